@@ -1,71 +1,218 @@
-//! A high-level way to load collections of asset handles as resources.
+//! Progress and failure reporting for the asset collections loaded on the loading screen.
+//!
+//! When assets fail, the game can still continue with [`substitute_placeholders`], which fills
+//! in checkerboard images where it can and inserts every collection that never finished.
 
-use std::collections::VecDeque;
+use std::any::type_name;
 
-use bevy::prelude::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_asset_loader::prelude::*;
+
+use crate::{game::aseprite::Aseprite, screens::Screen};
+
+const PLACEHOLDER_CHECK_SIZE: u32 = 4;
+const PLACEHOLDER_COLORS: [[u8; 4]; 2] = [[255, 0, 255, 255], [0, 0, 0, 255]];
+const PLACEHOLDER_SIZE: u32 = 16;
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<ResourceHandles>();
-    app.add_systems(PreUpdate, load_resource_assets);
+    app.init_resource::<LoadingProgress>();
+    app.init_resource::<TrackedCollections>();
+
+    app.add_systems(OnEnter(Screen::Loading), start_tracking);
 }
 
-pub trait LoadResource {
-    /// This will load the [`Resource`] as an [`Asset`]. When all of its asset dependencies
-    /// have been loaded, it will be inserted as a resource. This ensures that the resource only
-    /// exists when the assets are ready.
-    fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self;
+pub trait LoadCollection {
+    /// Loads the [`AssetCollection`] on the loading screen, where its progress and any failed
+    /// assets are reported.
+    fn load_collection<A: AssetCollection>(&mut self) -> &mut Self;
 }
 
-impl LoadResource for App {
-    fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self {
-        self.init_asset::<T>();
-        let world = self.world_mut();
-        let value = T::from_world(world);
-        let assets = world.resource::<AssetServer>();
-        let handle = assets.add(value);
-        let mut handles = world.resource_mut::<ResourceHandles>();
-        handles
-            .waiting
-            .push_back((handle.untyped(), |world, handle| {
-                let assets = world.resource::<Assets<T>>();
-                if let Some(value) = assets.get(handle.id().typed::<T>()) {
-                    world.insert_resource(value.clone());
-                }
-            }));
+impl LoadCollection for App {
+    fn load_collection<A: AssetCollection>(&mut self) -> &mut Self {
+        self.configure_loading_state(
+            LoadingStateConfig::new(Screen::Loading).load_collection::<A>(),
+        );
+
+        let name = collection_name::<A>();
+
+        self.world_mut()
+            .get_resource_or_init::<TrackedCollections>()
+            .0
+            .push(TrackedCollection {
+                insert: insert_collection::<A>,
+                load: A::load,
+                name,
+            });
+
         self
     }
 }
 
-/// A function that inserts a loaded resource.
-type InsertLoadedResource = fn(&mut World, &UntypedHandle);
+#[derive(Clone)]
+struct TrackedCollection {
+    // Inserts the collection's resource if loading never got to it
+    insert: fn(&mut World),
+    // Gets the handles of the collection's assets
+    load: fn(&mut World) -> Vec<UntypedHandle>,
+    name: &'static str,
+}
+
+// Every collection registered with `load_collection`
+#[derive(Resource, Default)]
+struct TrackedCollections(Vec<TrackedCollection>);
 
+/// Handles of every asset collection being loaded, by collection name. The handles are the same
+/// ones `bevy_asset_loader` is waiting on, since the asset server hands out one handle per path.
 #[derive(Resource, Default)]
-pub struct ResourceHandles {
-    // Use a queue for waiting assets so they can be cycled through and moved to
-    // `finished` one at a time.
-    waiting: VecDeque<(UntypedHandle, InsertLoadedResource)>,
-    finished: Vec<UntypedHandle>,
+pub struct LoadingProgress {
+    collections: Vec<(&'static str, Vec<UntypedHandle>)>,
 }
 
-impl ResourceHandles {
-    /// Returns true if all requested [`Asset`]s have finished loading and are available as [`Resource`]s.
-    pub fn is_all_done(&self) -> bool {
-        self.waiting.is_empty()
+impl LoadingProgress {
+    /// Assets loaded so far, including their dependencies, and assets in total.
+    pub fn counts(&self, asset_server: &AssetServer) -> (usize, usize) {
+        let handles = self.collections.iter().flat_map(|(_, handles)| handles);
+
+        let done = handles
+            .clone()
+            .filter(|handle| asset_server.is_loaded_with_dependencies(handle.id()))
+            .count();
+
+        (done, handles.count())
+    }
+
+    /// Every asset that failed to load, as the collection's name and the asset's path.
+    pub fn failures(&self, asset_server: &AssetServer) -> Vec<(&'static str, String)> {
+        self.failed_handles(asset_server)
+            .map(|(name, handle)| {
+                let path = handle
+                    .path()
+                    .map_or_else(|| format!("{:?}", handle.id()), ToString::to_string);
+
+                (name, path)
+            })
+            .collect()
+    }
+
+    /// Whether any asset of the [`AssetCollection`] failed to load.
+    pub fn has_failed<A: AssetCollection>(&self, asset_server: &AssetServer) -> bool {
+        let name = collection_name::<A>();
+
+        self.failed_handles(asset_server)
+            .any(|(collection, _)| collection == name)
+    }
+
+    fn failed_handles<'a>(
+        &'a self,
+        asset_server: &'a AssetServer,
+    ) -> impl Iterator<Item = (&'static str, &'a UntypedHandle)> {
+        self.collections
+            .iter()
+            .flat_map(|(name, handles)| handles.iter().map(move |handle| (*name, handle)))
+            .filter(|(_, handle)| {
+                asset_server
+                    .get_recursive_dependency_load_state(handle.id())
+                    .is_some_and(|load_state| load_state.is_failed())
+            })
+    }
+
+    /// Loads the failed assets again, for another attempt at the loading screen.
+    pub fn reload_failed(&self, asset_server: &AssetServer) {
+        for (_, path) in self.failures(asset_server) {
+            asset_server.reload(path);
+        }
     }
 }
 
-fn load_resource_assets(world: &mut World) {
-    world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
-        world.resource_scope(|world, assets: Mut<AssetServer>| {
-            for _ in 0..resource_handles.waiting.len() {
-                let (handle, insert_fn) = resource_handles.waiting.pop_front().unwrap();
-                if assets.is_loaded_with_dependencies(&handle) {
-                    insert_fn(world, &handle);
-                    resource_handles.finished.push(handle);
-                } else {
-                    resource_handles.waiting.push_back((handle, insert_fn));
-                }
-            }
-        });
-    });
+// Short type name, e.g. "GameAssets"
+fn collection_name<A: AssetCollection>() -> &'static str {
+    type_name::<A>().rsplit("::").next().unwrap_or_default()
+}
+
+fn insert_collection<A: AssetCollection>(world: &mut World) {
+    if !world.contains_resource::<A>() {
+        let collection = A::create(world);
+        world.insert_resource(collection);
+    }
+}
+
+fn start_tracking(world: &mut World) {
+    let tracked = world.resource::<TrackedCollections>().0.clone();
+
+    let collections = tracked
+        .into_iter()
+        .map(|collection| (collection.name, (collection.load)(world)))
+        .collect();
+
+    world.insert_resource(LoadingProgress { collections });
+}
+
+/// Lets the game go on after assets failed to load. Failed images and Aseprite files are replaced
+/// with a checkerboard, and collections that never finished loading are inserted anyway, so
+/// systems that need them don't panic. Anything else that failed, like fonts and audio, is left
+/// missing.
+pub fn substitute_placeholders(world: &mut World) {
+    let failed_handles = {
+        let asset_server = world.resource::<AssetServer>();
+
+        world
+            .resource::<LoadingProgress>()
+            .failed_handles(asset_server)
+            .map(|(_, handle)| handle.clone())
+            .collect::<Vec<_>>()
+    };
+
+    for handle in failed_handles {
+        if let Ok(handle) = handle.clone().try_typed::<Image>() {
+            let _ = world
+                .resource_mut::<Assets<Image>>()
+                .insert(&handle, placeholder_image());
+        } else if let Ok(handle) = handle.try_typed::<Aseprite>() {
+            let image = world
+                .resource_mut::<Assets<Image>>()
+                .add(placeholder_image());
+            let layout = world.resource_mut::<Assets<TextureAtlasLayout>>().add(
+                TextureAtlasLayout::from_grid(UVec2::splat(PLACEHOLDER_SIZE), 1, 1, None, None),
+            );
+
+            let _ = world
+                .resource_mut::<Assets<Aseprite>>()
+                .insert(&handle, Aseprite::placeholder(image, layout));
+        }
+    }
+
+    let tracked = world.resource::<TrackedCollections>().0.clone();
+
+    for collection in tracked {
+        (collection.insert)(world);
+    }
+}
+
+// Magenta and black checks, so a missing image is obvious rather than invisible
+fn placeholder_image() -> Image {
+    let pixels = (0..PLACEHOLDER_SIZE)
+        .flat_map(|y| {
+            (0..PLACEHOLDER_SIZE).flat_map(move |x| {
+                let check = (x / PLACEHOLDER_CHECK_SIZE + y / PLACEHOLDER_CHECK_SIZE) % 2;
+
+                PLACEHOLDER_COLORS[check as usize]
+            })
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: PLACEHOLDER_SIZE,
+            height: PLACEHOLDER_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
 }
@@ -1,9 +1,15 @@
-use bevy::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+
+use crate::settings::Settings;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
-        apply_global_volume.run_if(resource_changed::<GlobalVolume>),
+        (
+            apply_volume_setting.run_if(resource_changed::<Settings>),
+            apply_global_volume.run_if(resource_changed::<GlobalVolume>),
+        )
+            .chain(),
     );
 }
 
@@ -33,6 +39,15 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
+/// Keeps [`GlobalVolume`] in line with the saved master volume.
+fn apply_volume_setting(mut global_volume: ResMut<GlobalVolume>, settings: Res<Settings>) {
+    let volume = Volume::Linear(settings.volume_factor());
+
+    if global_volume.volume != volume {
+        global_volume.volume = volume;
+    }
+}
+
 /// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
@@ -0,0 +1,195 @@
+//! Window and camera scaling, driven by the display settings.
+//!
+//! The game camera normally stretches `GAME_WIDTH`×`GAME_HEIGHT` over the whole window. With
+//! pixel perfect on, it renders into a canvas texture at the game's resolution instead, which a
+//! second camera draws at the largest whole-number scale that fits, letterboxing the rest.
+//! Sprites under a [`PixelSnap`] entity are also snapped to whole pixels.
+
+use bevy::{
+    camera::{RenderTarget, visibility::RenderLayers},
+    prelude::*,
+    render::render_resource::TextureFormat,
+    window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode, WindowRef},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{GAME_HEIGHT, GAME_WIDTH, settings::Settings};
+
+// Only the canvas and UI are drawn by the window camera
+const CANVAS_LAYER: RenderLayers = RenderLayers::layer(1);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, spawn_canvas);
+    app.add_systems(
+        Update,
+        (
+            apply_window_settings.run_if(resource_changed::<Settings>),
+            apply_pixel_perfect,
+        )
+            .chain(),
+    );
+    app.add_systems(
+        PostUpdate,
+        snap_to_pixels
+            .after(TransformSystems::Propagate)
+            .run_if(is_pixel_perfect),
+    );
+}
+
+/// Sprites below this entity are drawn at whole-pixel positions when pixel perfect is on.
+#[derive(Component)]
+pub struct PixelSnap;
+
+#[derive(Component)]
+struct Canvas;
+
+#[derive(Resource)]
+struct CanvasImage(Handle<Image>);
+
+#[derive(Component)]
+struct WindowCamera;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    BorderlessFullscreen,
+}
+
+impl DisplayMode {
+    /// String key for the mode's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Self::Windowed => "display.windowed",
+            Self::BorderlessFullscreen => "display.borderless_fullscreen",
+        }
+    }
+}
+
+fn apply_window_settings(
+    mut last_applied: Local<Option<(DisplayMode, bool, u32)>>,
+    settings: Res<Settings>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    // Only touch the window when a display setting changed, so other settings don't undo a
+    // window the player resized by hand
+    let display_settings = (settings.display_mode, settings.vsync, settings.window_scale);
+
+    if *last_applied == Some(display_settings) {
+        return;
+    }
+
+    *last_applied = Some(display_settings);
+
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+
+    match settings.display_mode {
+        DisplayMode::Windowed => {
+            window.mode = WindowMode::Windowed;
+            window.resolution.set(
+                GAME_WIDTH * settings.window_scale as f32,
+                GAME_HEIGHT * settings.window_scale as f32,
+            );
+        }
+        DisplayMode::BorderlessFullscreen => {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+        }
+    }
+}
+
+fn spawn_canvas(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(Image::new_target_texture(
+        GAME_WIDTH as u32,
+        GAME_HEIGHT as u32,
+        TextureFormat::bevy_default(),
+        None,
+    ));
+
+    commands.spawn((
+        Name::new("Canvas"),
+        Canvas,
+        Sprite::from_image(image.clone()),
+        CANVAS_LAYER,
+        Visibility::Hidden,
+    ));
+    commands.spawn((
+        Name::new("Window Camera"),
+        WindowCamera,
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        Msaa::Off,
+        CANVAS_LAYER,
+        IsDefaultUiCamera,
+    ));
+
+    commands.insert_resource(CanvasImage(image));
+}
+
+fn apply_pixel_perfect(
+    canvas: Single<(&mut Transform, &mut Visibility), With<Canvas>>,
+    canvas_image: Res<CanvasImage>,
+    mut game_camera: Single<&mut RenderTarget, (With<Camera2d>, Without<WindowCamera>)>,
+    settings: Res<Settings>,
+    mut window_camera: Single<&mut Camera, With<WindowCamera>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+) {
+    let (mut transform, mut visibility) = canvas.into_inner();
+    let is_rendering_to_canvas = matches!(**game_camera, RenderTarget::Image(_));
+
+    if settings.pixel_perfect != is_rendering_to_canvas {
+        if settings.pixel_perfect {
+            **game_camera = RenderTarget::Image(canvas_image.0.clone().into());
+            window_camera.clear_color = ClearColorConfig::Default;
+            *visibility = Visibility::Inherited;
+        } else {
+            **game_camera = RenderTarget::Window(WindowRef::Primary);
+            window_camera.clear_color = ClearColorConfig::None;
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    // The window camera works in logical pixels, but whole-number scaling has to happen in
+    // physical ones
+    let pixel_scale = (window.physical_width() as f32 / GAME_WIDTH)
+        .min(window.physical_height() as f32 / GAME_HEIGHT)
+        .floor()
+        .max(1.0);
+    let scale = Vec3::splat(pixel_scale / window.scale_factor());
+
+    if transform.scale != scale {
+        transform.scale = scale;
+    }
+}
+
+fn is_pixel_perfect(settings: Res<Settings>) -> bool {
+    settings.pixel_perfect
+}
+
+// Runs after transform propagation so movement can keep its sub-pixel positions
+fn snap_to_pixels(
+    children_query: Query<&Children>,
+    pixel_snap_query: Query<Entity, With<PixelSnap>>,
+    mut sprite_query: Query<&mut GlobalTransform, With<Sprite>>,
+) {
+    for entity in pixel_snap_query {
+        for descendant in children_query.iter_descendants(entity) {
+            let Ok(mut global_transform) = sprite_query.get_mut(descendant) else {
+                continue;
+            };
+
+            let mut affine = global_transform.affine();
+            affine.translation.x = affine.translation.x.round();
+            affine.translation.y = affine.translation.y.round();
+
+            *global_transform = GlobalTransform::from(affine);
+        }
+    }
+}
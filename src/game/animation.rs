@@ -1,35 +1,134 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
 
 use crate::{
     AppSystems, PausableSystems,
-    game::{game_state::GameState, minigame_manager::MinigameManager},
+    game::{game_state::GameState, gameplay_time::Gameplay, minigame_manager::MinigameManager},
+    settings::Settings,
 };
 
 const BASE_ANIMATION_SPEED: u64 = 250;
+// Frames last twice as long with reduced motion
+const REDUCED_MOTION_SPEED: f32 = 0.5;
 
-#[derive(Clone, Eq, PartialEq)]
-enum AnimationType {
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AnimationMode {
+    // Start over after the last frame
+    #[default]
     Looping,
-    OneOff,
+    // Hold the last frame and emit `AnimationFinished`
+    OneShot,
+    // Play forwards then backwards, without repeating the end frames
+    PingPong,
 }
 
+#[derive(Clone, Debug)]
+pub struct AnimationFrame {
+    pub duration: Duration,
+    pub index: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    pub frames: Vec<AnimationFrame>,
+    pub mode: AnimationMode,
+    // Clip to switch to once a one-shot clip finishes
+    pub next: Option<String>,
+}
+
+// Builders for the clips the Aseprite tests expect
+#[cfg(test)]
+impl AnimationClip {
+    pub fn new(frames: &[usize], mode: AnimationMode) -> Self {
+        Self {
+            frames: frames
+                .iter()
+                .map(|index| AnimationFrame {
+                    duration: Duration::from_millis(BASE_ANIMATION_SPEED),
+                    index: *index,
+                })
+                .collect(),
+            mode,
+            next: None,
+        }
+    }
+
+    pub fn looping(frames: &[usize]) -> Self {
+        Self::new(frames, AnimationMode::Looping)
+    }
+
+    pub fn one_shot(frames: &[usize]) -> Self {
+        Self::new(frames, AnimationMode::OneShot)
+    }
+
+    pub fn ping_pong(frames: &[usize]) -> Self {
+        Self::new(frames, AnimationMode::PingPong)
+    }
+
+    pub fn then(mut self, next: impl Into<String>) -> Self {
+        self.next = Some(next.into());
+
+        self
+    }
+
+    pub fn with_frame_duration(mut self, duration: Duration) -> Self {
+        for frame in &mut self.frames {
+            frame.duration = duration;
+        }
+
+        self
+    }
+}
+
+#[derive(Debug, EntityEvent)]
+// A one-shot clip reached its last frame
+pub struct AnimationFinished {
+    pub clip: String,
+    pub entity: Entity,
+}
+
+// Plays one of a set of named clips, usually imported from an `Aseprite` file. Switching between
+// clips with `play` makes this a small state machine, e.g. "idle" and "walk", or "bloom" followed
+// by "bloomed".
 #[derive(Clone, Component)]
 pub struct Animation {
-    animation_type: AnimationType,
+    clips: HashMap<String, AnimationClip>,
+    current_clip: String,
     current_frame: usize,
-    frames: Vec<usize>,
+    // Clip that quietly stands in for any missing one, instead of a warning
+    fallback_clip: Option<String>,
+    is_finished: bool,
+    is_reversing: bool,
     minigame_key: Option<&'static str>,
     timer: Timer,
 }
 
 impl Animation {
-    pub fn looping(frames: &[usize]) -> Self {
+    pub fn from_clips(
+        mut clips: HashMap<String, AnimationClip>,
+        clip_name: &str,
+        fallback_clip: Option<String>,
+    ) -> Self {
+        Self::resolve_clip(&mut clips, fallback_clip.as_deref(), clip_name);
+
+        let timer = Timer::new(
+            clips
+                .get(clip_name)
+                .map(|clip| Self::frame_duration(clip, 0))
+                .unwrap_or(Duration::from_millis(BASE_ANIMATION_SPEED)),
+            TimerMode::Once,
+        );
+
         Self {
-            current_frame: frames[0],
-            frames: frames.iter().copied().collect(),
-            ..default()
+            clips,
+            current_clip: clip_name.to_string(),
+            current_frame: 0,
+            fallback_clip,
+            is_finished: false,
+            is_reversing: false,
+            minigame_key: None,
+            timer,
         }
     }
 
@@ -38,20 +137,111 @@ impl Animation {
 
         self
     }
-}
 
-impl Default for Animation {
-    fn default() -> Self {
-        Self {
-            animation_type: AnimationType::Looping,
-            current_frame: 0,
-            frames: Vec::new(),
-            minigame_key: None,
-            timer: Timer::new(
-                Duration::from_millis(BASE_ANIMATION_SPEED),
-                TimerMode::Repeating,
-            ),
+    pub fn current_clip(&self) -> &str {
+        &self.current_clip
+    }
+
+    // Switch to another clip, restarting it. Playing the current clip does nothing.
+    pub fn play(&mut self, clip_name: &str) {
+        if self.current_clip == clip_name {
+            return;
+        }
+
+        if !Self::resolve_clip(&mut self.clips, self.fallback_clip.as_deref(), clip_name) {
+            return;
+        }
+
+        let clip = &self.clips[clip_name];
+
+        self.timer = Timer::new(Self::frame_duration(clip, 0), TimerMode::Once);
+        self.current_clip = clip_name.to_string();
+        self.current_frame = 0;
+        self.is_finished = false;
+        self.is_reversing = false;
+    }
+
+    // Whether the clip exists, after filling it in with a copy of the fallback clip if there is one
+    fn resolve_clip(
+        clips: &mut HashMap<String, AnimationClip>,
+        fallback_clip: Option<&str>,
+        clip_name: &str,
+    ) -> bool {
+        if clips.contains_key(clip_name) {
+            return true;
         }
+
+        let Some(clip) = fallback_clip
+            .and_then(|fallback| clips.get(fallback))
+            .cloned()
+        else {
+            warn!("No animation clip named \"{}\"", clip_name);
+            return false;
+        };
+
+        clips.insert(clip_name.to_string(), clip);
+
+        true
+    }
+
+    fn atlas_index(&self) -> Option<usize> {
+        self.clips
+            .get(&self.current_clip)?
+            .frames
+            .get(self.current_frame)
+            .map(|frame| frame.index)
+    }
+
+    fn frame_duration(clip: &AnimationClip, frame: usize) -> Duration {
+        clip.frames
+            .get(frame)
+            .map(|frame| frame.duration)
+            .unwrap_or(Duration::from_millis(BASE_ANIMATION_SPEED))
+    }
+
+    // Move to the next frame, returning true if a one-shot clip just finished
+    fn advance(&mut self) -> bool {
+        let Some(clip) = self.clips.get(&self.current_clip) else {
+            return false;
+        };
+
+        let last_frame = clip.frames.len().saturating_sub(1);
+
+        match clip.mode {
+            AnimationMode::Looping => {
+                self.current_frame = if self.current_frame >= last_frame {
+                    0
+                } else {
+                    self.current_frame + 1
+                };
+            }
+            AnimationMode::OneShot => {
+                if self.current_frame >= last_frame {
+                    self.is_finished = true;
+
+                    return true;
+                }
+
+                self.current_frame += 1;
+            }
+            AnimationMode::PingPong => {
+                if last_frame == 0 {
+                    self.current_frame = 0;
+                } else if self.is_reversing {
+                    self.current_frame = self.current_frame.saturating_sub(1);
+                    self.is_reversing = self.current_frame != 0;
+                } else {
+                    self.current_frame = (self.current_frame + 1).min(last_frame);
+                    self.is_reversing = self.current_frame == last_frame;
+                }
+            }
+        }
+
+        let duration = Self::frame_duration(clip, self.current_frame);
+
+        self.timer = Timer::new(duration, TimerMode::Once);
+
+        false
     }
 }
 
@@ -60,9 +250,20 @@ fn animation_timer(
     mut commands: Commands,
     game_state: Res<State<GameState>>,
     minigame_manager: Res<MinigameManager>,
-    time: Res<Time>,
+    settings: Res<Settings>,
+    time: Res<Time<Gameplay>>,
 ) {
+    let delta = if settings.reduced_motion {
+        time.delta().mul_f32(REDUCED_MOTION_SPEED)
+    } else {
+        time.delta()
+    };
+
     for (mut animation, entity, mut sprite) in animation_query {
+        let Some(current_index) = sprite.texture_atlas.as_ref().map(|atlas| atlas.index) else {
+            continue;
+        };
+
         if let Some(minigame_key) = animation.minigame_key {
             if game_state.get() != &GameState::Minigame {
                 continue;
@@ -73,25 +274,29 @@ fn animation_timer(
             }
         }
 
-        animation.timer.tick(time.delta());
+        if !animation.is_finished {
+            animation.timer.tick(delta);
 
-        if animation.timer.just_finished() {
-            let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
-                return;
-            };
+            if animation.timer.just_finished() && animation.advance() {
+                let clip = animation.current_clip.clone();
+                let next = animation
+                    .clips
+                    .get(&clip)
+                    .and_then(|clip| clip.next.clone());
 
-            animation.current_frame += 1;
-
-            if animation.current_frame >= animation.frames.len() {
-                if animation.animation_type == AnimationType::OneOff {
-                    commands.entity(entity).remove::<Animation>();
-                    continue;
+                if let Some(next) = next {
+                    animation.play(&next);
                 }
 
-                animation.current_frame = 0;
+                commands.trigger(AnimationFinished { clip, entity });
             }
+        }
 
-            texture_atlas.index = animation.frames[animation.current_frame];
+        if let Some(atlas_index) = animation.atlas_index()
+            && atlas_index != current_index
+            && let Some(texture_atlas) = sprite.texture_atlas.as_mut()
+        {
+            texture_atlas.index = atlas_index;
         }
     }
 }
@@ -0,0 +1,839 @@
+use std::{io::Read, time::Duration};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, RenderAssetUsages, io::Reader},
+    platform::collections::HashMap,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use flate2::read::ZlibDecoder;
+
+use crate::game::animation::{Animation, AnimationClip, AnimationFrame, AnimationMode};
+
+// Clip spanning every frame, so untagged files still animate
+pub const DEFAULT_CLIP: &str = "default";
+
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_USER_DATA: u16 = 0x2020;
+const COLOR_DEPTH_RGBA: u16 = 32;
+const FILE_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+const HEADER_SIZE: usize = 128;
+const LAYER_FLAG_REFERENCE: u16 = 64;
+const LAYER_FLAG_VISIBLE: u16 = 1;
+const LAYER_TYPE_IMAGE: u16 = 0;
+const USER_DATA_FLAG_TEXT: u32 = 1;
+
+// A sprite imported from an Aseprite file. Visible layers are flattened into a texture atlas
+// with one cell per frame, and every tag becomes a named clip:
+// - The tag's direction picks looping, reversed, or ping-pong playback
+// - A repeat count makes it a one-shot clip that plays that many times
+// - The tag's user data text names the clip that follows once a one-shot clip finishes
+#[derive(Asset, Debug, TypePath)]
+pub struct Aseprite {
+    pub clips: HashMap<String, AnimationClip>,
+    // Clip played in place of any clip the file doesn't have
+    pub fallback_clip: Option<String>,
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl Aseprite {
+    // Single-frame stand-in for a file that failed to load. Only the default clip exists, and it
+    // quietly stands in for every clip the game asks for.
+    pub fn placeholder(image: Handle<Image>, layout: Handle<TextureAtlasLayout>) -> Self {
+        Self {
+            clips: HashMap::from([(
+                DEFAULT_CLIP.to_string(),
+                AnimationClip {
+                    frames: vec![AnimationFrame {
+                        duration: Duration::from_millis(1000),
+                        index: 0,
+                    }],
+                    mode: AnimationMode::Looping,
+                    next: None,
+                },
+            )]),
+            fallback_clip: Some(DEFAULT_CLIP.to_string()),
+            image,
+            layout,
+        }
+    }
+
+    pub fn animation(&self, clip_name: &str) -> Animation {
+        Animation::from_clips(self.clips.clone(), clip_name, self.fallback_clip.clone())
+    }
+
+    // A sprite showing the first frame of a clip
+    pub fn sprite(&self, clip_name: &str) -> Sprite {
+        let index = self
+            .clips
+            .get(clip_name)
+            .and_then(|clip| clip.frames.first())
+            .map(|frame| frame.index)
+            .unwrap_or_default();
+
+        Sprite::from_atlas_image(
+            self.image.clone(),
+            TextureAtlas {
+                layout: self.layout.clone(),
+                index,
+            },
+        )
+    }
+}
+
+struct Cel {
+    content: CelContent,
+    layer: usize,
+    opacity: u8,
+    x: i32,
+    y: i32,
+}
+
+enum CelContent {
+    Image {
+        height: usize,
+        pixels: Vec<u8>,
+        width: usize,
+    },
+    // Shares the cel of the same layer in another frame
+    Linked(usize),
+    // Tilemaps aren't supported
+    Unsupported,
+}
+
+struct Frame {
+    cels: Vec<Cel>,
+    duration: Duration,
+}
+
+struct Layer {
+    child_level: u16,
+    flags: u16,
+    layer_type: u16,
+    opacity: u8,
+}
+
+struct Tag {
+    direction: u8,
+    from: usize,
+    name: String,
+    next: Option<String>,
+    repeat: u16,
+    to: usize,
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], BevyError> {
+        let end = self.position + length;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or("unexpected end of Aseprite data")?;
+
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<(), BevyError> {
+        self.take(length).map(|_| ())
+    }
+
+    fn byte(&mut self) -> Result<u8, BevyError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn word(&mut self) -> Result<u16, BevyError> {
+        let bytes = self.take(2)?;
+
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn short(&mut self) -> Result<i16, BevyError> {
+        Ok(self.word()? as i16)
+    }
+
+    fn dword(&mut self) -> Result<u32, BevyError> {
+        let bytes = self.take(4)?;
+
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, BevyError> {
+        let length = self.word()? as usize;
+
+        Ok(String::from_utf8_lossy(self.take(length)?).into_owned())
+    }
+}
+
+fn read_layer(cursor: &mut Cursor) -> Result<Layer, BevyError> {
+    let flags = cursor.word()?;
+    let layer_type = cursor.word()?;
+    let child_level = cursor.word()?;
+    // Default width and height, then blend mode (only normal blending is supported)
+    cursor.skip(6)?;
+    let opacity = cursor.byte()?;
+
+    Ok(Layer {
+        child_level,
+        flags,
+        layer_type,
+        opacity,
+    })
+}
+
+fn read_cel(cursor: &mut Cursor) -> Result<Cel, BevyError> {
+    let layer = cursor.word()? as usize;
+    let x = cursor.short()? as i32;
+    let y = cursor.short()? as i32;
+    let opacity = cursor.byte()?;
+    let cel_type = cursor.word()?;
+    // Z-index and reserved bytes
+    cursor.skip(7)?;
+
+    let content = match cel_type {
+        0 | 2 => {
+            let width = cursor.word()? as usize;
+            let height = cursor.word()? as usize;
+            let data = cursor.take(cursor.bytes.len() - cursor.position)?;
+
+            let pixels = if cel_type == 2 {
+                let mut pixels = Vec::with_capacity(width * height * 4);
+                ZlibDecoder::new(data).read_to_end(&mut pixels)?;
+                pixels
+            } else {
+                data.to_vec()
+            };
+
+            if pixels.len() < width * height * 4 {
+                return Err("Aseprite cel is smaller than its size".into());
+            }
+
+            CelContent::Image {
+                height,
+                pixels,
+                width,
+            }
+        }
+        1 => CelContent::Linked(cursor.word()? as usize),
+        _ => CelContent::Unsupported,
+    };
+
+    Ok(Cel {
+        content,
+        layer,
+        opacity,
+        x,
+        y,
+    })
+}
+
+fn read_tags(cursor: &mut Cursor) -> Result<Vec<Tag>, BevyError> {
+    let count = cursor.word()?;
+    cursor.skip(8)?;
+
+    (0..count)
+        .map(|_| {
+            let from = cursor.word()? as usize;
+            let to = cursor.word()? as usize;
+            let direction = cursor.byte()?;
+            let repeat = cursor.word()?;
+            // Reserved bytes and the deprecated tag color
+            cursor.skip(10)?;
+            let name = cursor.string()?;
+
+            Ok(Tag {
+                direction,
+                from,
+                name,
+                next: None,
+                repeat,
+                to,
+            })
+        })
+        .collect()
+}
+
+fn read_user_data_text(cursor: &mut Cursor) -> Result<Option<String>, BevyError> {
+    let flags = cursor.dword()?;
+
+    if flags & USER_DATA_FLAG_TEXT == 0 {
+        return Ok(None);
+    }
+
+    let text = cursor.string()?.trim().to_string();
+
+    Ok((!text.is_empty()).then_some(text))
+}
+
+// Whether a layer and all of the groups it's nested in are visible
+fn is_layer_visible(layers: &[Layer], layer_index: usize) -> bool {
+    let Some(layer) = layers.get(layer_index) else {
+        return false;
+    };
+
+    if layer.flags & LAYER_FLAG_VISIBLE == 0 || layer.flags & LAYER_FLAG_REFERENCE != 0 {
+        return false;
+    }
+
+    let mut child_level = layer.child_level;
+
+    for parent in layers[..layer_index].iter().rev() {
+        if child_level == 0 {
+            break;
+        }
+
+        if parent.child_level < child_level {
+            if parent.flags & LAYER_FLAG_VISIBLE == 0 {
+                return false;
+            }
+
+            child_level = parent.child_level;
+        }
+    }
+
+    true
+}
+
+// Draw a cel over a frame-sized buffer with normal blending. Colors stay in 0-255 while alpha
+// is kept in 0-1.
+fn blend_cel(
+    frame_pixels: &mut [f32],
+    frame_size: UVec2,
+    cel: &Cel,
+    (width, height, pixels): (usize, usize, &[u8]),
+    opacity: f32,
+) {
+    for cel_y in 0..height {
+        for cel_x in 0..width {
+            let x = cel.x + cel_x as i32;
+            let y = cel.y + cel_y as i32;
+
+            if x < 0 || y < 0 || x >= frame_size.x as i32 || y >= frame_size.y as i32 {
+                continue;
+            }
+
+            let source = &pixels[(cel_y * width + cel_x) * 4..][..4];
+            let source_alpha = source[3] as f32 / 255.0 * opacity;
+
+            if source_alpha <= 0.0 {
+                continue;
+            }
+
+            let target =
+                &mut frame_pixels[(y as usize * frame_size.x as usize + x as usize) * 4..][..4];
+            let target_alpha = target[3] * (1.0 - source_alpha);
+            let alpha = source_alpha + target_alpha;
+
+            for channel in 0..3 {
+                target[channel] = (source[channel] as f32 * source_alpha
+                    + target[channel] * target_alpha)
+                    / alpha;
+            }
+
+            target[3] = alpha;
+        }
+    }
+}
+
+fn tag_clip(tag: &Tag, frames: &[AnimationFrame]) -> AnimationClip {
+    let mut pass = frames
+        .get(tag.from..=tag.to.min(frames.len().saturating_sub(1)))
+        .unwrap_or_default()
+        .to_vec();
+
+    // 1: reverse, 3: ping-pong starting in reverse
+    if tag.direction == 1 || tag.direction == 3 {
+        pass.reverse();
+    }
+
+    let is_ping_pong = tag.direction >= 2;
+
+    if tag.repeat == 0 {
+        return AnimationClip {
+            frames: pass,
+            mode: if is_ping_pong {
+                AnimationMode::PingPong
+            } else {
+                AnimationMode::Looping
+            },
+            next: tag.next.clone(),
+        };
+    }
+
+    // Unroll the repeats into a single pass that plays once
+    let mut clip_frames = Vec::new();
+
+    for repeat in 0..tag.repeat {
+        if is_ping_pong && repeat % 2 == 1 {
+            clip_frames.extend(pass.iter().rev().skip(1).cloned());
+        } else if is_ping_pong && repeat > 0 {
+            clip_frames.extend(pass.iter().skip(1).cloned());
+        } else {
+            clip_frames.extend(pass.iter().cloned());
+        }
+    }
+
+    AnimationClip {
+        frames: clip_frames,
+        mode: AnimationMode::OneShot,
+        next: tag.next.clone(),
+    }
+}
+
+// Everything read from an Aseprite file, before the image and layout become assets
+struct AsepriteData {
+    clips: HashMap<String, AnimationClip>,
+    image: Image,
+    layout: TextureAtlasLayout,
+}
+
+fn read_aseprite(bytes: &[u8]) -> Result<AsepriteData, BevyError> {
+    let mut cursor = Cursor::new(bytes);
+    cursor.skip(4)?;
+
+    if cursor.word()? != FILE_MAGIC {
+        return Err("not an Aseprite file".into());
+    }
+
+    let frame_count = cursor.word()? as usize;
+    let frame_size = UVec2::new(cursor.word()? as u32, cursor.word()? as u32);
+
+    if cursor.word()? != COLOR_DEPTH_RGBA {
+        return Err("only RGBA Aseprite files are supported".into());
+    }
+
+    let has_layer_opacity = cursor.dword()? & 1 != 0;
+    cursor.position = HEADER_SIZE;
+
+    let mut frames = Vec::with_capacity(frame_count);
+    let mut layers = Vec::new();
+    let mut tags = Vec::new();
+
+    for _ in 0..frame_count {
+        let frame_start = cursor.position;
+        let frame_length = cursor.dword()? as usize;
+
+        if cursor.word()? != FRAME_MAGIC {
+            return Err("corrupt Aseprite frame".into());
+        }
+
+        let old_chunk_count = cursor.word()? as usize;
+        let duration = Duration::from_millis(cursor.word()? as u64);
+        cursor.skip(2)?;
+        let chunk_count = match cursor.dword()? as usize {
+            0 => old_chunk_count,
+            chunk_count => chunk_count,
+        };
+
+        let mut cels = Vec::new();
+        // User data chunks after the tags chunk belong to each tag in turn
+        let mut tag_user_data = None;
+
+        for _ in 0..chunk_count {
+            let chunk_start = cursor.position;
+            let chunk_length = cursor.dword()? as usize;
+            let chunk_type = cursor.word()?;
+            let mut chunk = Cursor::new(cursor.take(chunk_length.saturating_sub(6))?);
+
+            match chunk_type {
+                CHUNK_CEL => cels.push(read_cel(&mut chunk)?),
+                CHUNK_LAYER => layers.push(read_layer(&mut chunk)?),
+                CHUNK_TAGS => {
+                    tags = read_tags(&mut chunk)?;
+                    tag_user_data = Some(0);
+                }
+                CHUNK_USER_DATA => {
+                    if let Some(tag_index) = tag_user_data.as_mut() {
+                        if let Some(tag) = tags.get_mut(*tag_index) {
+                            tag.next = read_user_data_text(&mut chunk)?;
+                        }
+
+                        *tag_index += 1;
+                    }
+                }
+                _ => {}
+            }
+
+            if chunk_type != CHUNK_USER_DATA && chunk_type != CHUNK_TAGS {
+                tag_user_data = None;
+            }
+
+            cursor.position = chunk_start + chunk_length;
+        }
+
+        frames.push(Frame { cels, duration });
+        cursor.position = frame_start + frame_length;
+    }
+
+    // Lay the frames out in a roughly square grid to keep the texture small
+    let columns = (frame_count as f32).sqrt().ceil().max(1.0) as u32;
+    let rows = (frame_count as u32).div_ceil(columns).max(1);
+    let atlas_size = frame_size * UVec2::new(columns, rows);
+    let mut atlas_pixels = vec![0; (atlas_size.x * atlas_size.y * 4) as usize];
+
+    for (frame_index, frame) in frames.iter().enumerate() {
+        let mut frame_pixels = vec![0.0; (frame_size.x * frame_size.y * 4) as usize];
+        let mut cels = frame.cels.iter().collect::<Vec<_>>();
+
+        cels.sort_by_key(|cel| cel.layer);
+
+        for cel in cels {
+            let Some(layer) = layers.get(cel.layer) else {
+                continue;
+            };
+
+            if layer.layer_type != LAYER_TYPE_IMAGE || !is_layer_visible(&layers, cel.layer) {
+                continue;
+            }
+
+            let content = match &cel.content {
+                CelContent::Linked(linked_frame) => frames
+                    .get(*linked_frame)
+                    .and_then(|frame| {
+                        frame
+                            .cels
+                            .iter()
+                            .find(|linked_cel| linked_cel.layer == cel.layer)
+                    })
+                    .map(|linked_cel| &linked_cel.content),
+                content => Some(content),
+            };
+
+            let Some(CelContent::Image {
+                height,
+                pixels,
+                width,
+            }) = content
+            else {
+                continue;
+            };
+
+            let layer_opacity = if has_layer_opacity {
+                layer.opacity as f32 / 255.0
+            } else {
+                1.0
+            };
+
+            blend_cel(
+                &mut frame_pixels,
+                frame_size,
+                cel,
+                (*width, *height, pixels),
+                cel.opacity as f32 / 255.0 * layer_opacity,
+            );
+        }
+
+        let cell =
+            UVec2::new(frame_index as u32 % columns, frame_index as u32 / columns) * frame_size;
+
+        for y in 0..frame_size.y {
+            for x in 0..frame_size.x {
+                let source = &frame_pixels[((y * frame_size.x + x) * 4) as usize..][..4];
+                let target = &mut atlas_pixels
+                    [(((cell.y + y) * atlas_size.x + cell.x + x) * 4) as usize..][..4];
+
+                target[0] = source[0].round() as u8;
+                target[1] = source[1].round() as u8;
+                target[2] = source[2].round() as u8;
+                target[3] = (source[3] * 255.0).round() as u8;
+            }
+        }
+    }
+
+    let animation_frames = frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| AnimationFrame {
+            duration: frame.duration,
+            index,
+        })
+        .collect::<Vec<_>>();
+
+    let mut clips = HashMap::from([(
+        DEFAULT_CLIP.to_string(),
+        AnimationClip {
+            frames: animation_frames.clone(),
+            mode: AnimationMode::Looping,
+            next: None,
+        },
+    )]);
+
+    for tag in &tags {
+        clips.insert(tag.name.clone(), tag_clip(tag, &animation_frames));
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: atlas_size.x,
+            height: atlas_size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        atlas_pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let layout = TextureAtlasLayout::from_grid(frame_size, columns, rows, None, None);
+
+    Ok(AsepriteData {
+        clips,
+        image,
+        layout,
+    })
+}
+
+#[derive(Default, TypePath)]
+struct AsepriteLoader;
+
+impl AssetLoader for AsepriteLoader {
+    type Asset = Aseprite;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Aseprite, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let data = read_aseprite(&bytes)?;
+
+        Ok(Aseprite {
+            clips: data.clips,
+            fallback_clip: None,
+            image: load_context.add_labeled_asset("image".to_string(), data.image),
+            layout: load_context.add_labeled_asset("layout".to_string(), data.layout),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite", "ase"]
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Aseprite>();
+    app.init_asset_loader::<AsepriteLoader>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOWER: &[u8] = include_bytes!("../../assets/images/relieve_flower.aseprite");
+    const STAR: &[u8] = include_bytes!("../../assets/images/observe_star.aseprite");
+
+    fn assert_clip(clip: &AnimationClip, expected: &AnimationClip) {
+        let frames = |clip: &AnimationClip| {
+            clip.frames
+                .iter()
+                .map(|frame| (frame.index, frame.duration))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(frames(clip), frames(expected));
+        assert_eq!(clip.mode, expected.mode);
+        assert_eq!(clip.next, expected.next);
+    }
+
+    fn chunk(chunk_type: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = ((data.len() + 6) as u32).to_le_bytes().to_vec();
+        bytes.extend(chunk_type.to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    // A visible image layer at full opacity
+    fn layer_chunk() -> Vec<u8> {
+        let mut data = vec![0; 18];
+        data[0] = LAYER_FLAG_VISIBLE as u8;
+        data[12] = 255;
+        chunk(CHUNK_LAYER, &data)
+    }
+
+    fn cel_chunk(cel_type: u16, content: &[u8]) -> Vec<u8> {
+        // Layer 0 at the origin, fully opaque
+        let mut data = vec![0, 0, 0, 0, 0, 0, 255];
+        data.extend(cel_type.to_le_bytes());
+        data.extend([0; 7]);
+        data.extend(content);
+        chunk(CHUNK_CEL, &data)
+    }
+
+    // A single-pixel cel
+    fn raw_cel_chunk(pixel: [u8; 4]) -> Vec<u8> {
+        let mut content = vec![1, 0, 1, 0];
+        content.extend(pixel);
+        cel_chunk(0, &content)
+    }
+
+    fn linked_cel_chunk(frame: u16) -> Vec<u8> {
+        cel_chunk(1, &frame.to_le_bytes())
+    }
+
+    // A 1x1 file with the given chunks in each frame
+    fn file(color_depth: u16, frames: &[Vec<Vec<u8>>]) -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE];
+        bytes[4..6].copy_from_slice(&FILE_MAGIC.to_le_bytes());
+        bytes[6..8].copy_from_slice(&(frames.len() as u16).to_le_bytes());
+        bytes[8..10].copy_from_slice(&1u16.to_le_bytes());
+        bytes[10..12].copy_from_slice(&1u16.to_le_bytes());
+        bytes[12..14].copy_from_slice(&color_depth.to_le_bytes());
+
+        for chunks in frames {
+            let data = chunks.concat();
+            bytes.extend(((data.len() + 16) as u32).to_le_bytes());
+            bytes.extend(FRAME_MAGIC.to_le_bytes());
+            bytes.extend((chunks.len() as u16).to_le_bytes());
+            bytes.extend(100u16.to_le_bytes());
+            bytes.extend([0; 6]);
+            bytes.extend(data);
+        }
+
+        let length = bytes.len() as u32;
+        bytes[0..4].copy_from_slice(&length.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn reads_header_and_frames() {
+        let data = read_aseprite(FLOWER).unwrap();
+
+        // Three 32x64 frames laid out in a 2x2 grid
+        assert_eq!(data.image.size(), UVec2::new(64, 128));
+        assert_eq!(data.layout.size, UVec2::new(64, 128));
+        assert_eq!(data.layout.textures.len(), 4);
+        assert_eq!(data.layout.textures[2].min, UVec2::new(0, 64));
+        assert!(
+            data.image
+                .data
+                .unwrap()
+                .chunks(4)
+                .any(|pixel| pixel[3] == 255)
+        );
+        assert_clip(
+            &data.clips[DEFAULT_CLIP],
+            &AnimationClip::looping(&[0, 1, 2]).with_frame_duration(Duration::from_millis(100)),
+        );
+    }
+
+    #[test]
+    fn reads_ping_pong_tags() {
+        let data = read_aseprite(STAR).unwrap();
+
+        assert_clip(
+            &data.clips["twinkle"],
+            &AnimationClip::ping_pong(&[0, 1]).with_frame_duration(Duration::from_millis(250)),
+        );
+    }
+
+    #[test]
+    fn reads_repeat_tags_and_next_clip_from_user_data() {
+        let data = read_aseprite(FLOWER).unwrap();
+
+        assert_clip(
+            &data.clips["bloom"],
+            &AnimationClip::one_shot(&[0, 1, 2])
+                .with_frame_duration(Duration::from_millis(100))
+                .then("bloomed"),
+        );
+        assert_clip(
+            &data.clips["closed"],
+            &AnimationClip::looping(&[0]).with_frame_duration(Duration::from_millis(100)),
+        );
+    }
+
+    #[test]
+    fn unrolls_tag_directions_and_repeats() {
+        let frames = AnimationClip::looping(&[0, 1, 2, 3]).frames;
+        let tag = |direction, repeat| Tag {
+            direction,
+            from: 1,
+            name: String::new(),
+            next: None,
+            repeat,
+            to: 3,
+        };
+
+        assert_clip(
+            &tag_clip(&tag(0, 0), &frames),
+            &AnimationClip::looping(&[1, 2, 3]),
+        );
+        assert_clip(
+            &tag_clip(&tag(1, 0), &frames),
+            &AnimationClip::looping(&[3, 2, 1]),
+        );
+        assert_clip(
+            &tag_clip(&tag(2, 0), &frames),
+            &AnimationClip::ping_pong(&[1, 2, 3]),
+        );
+        assert_clip(
+            &tag_clip(&tag(3, 0), &frames),
+            &AnimationClip::ping_pong(&[3, 2, 1]),
+        );
+        assert_clip(
+            &tag_clip(&tag(0, 2), &frames),
+            &AnimationClip::one_shot(&[1, 2, 3, 1, 2, 3]),
+        );
+        assert_clip(
+            &tag_clip(&tag(2, 3), &frames),
+            &AnimationClip::one_shot(&[1, 2, 3, 2, 1, 2, 3]),
+        );
+    }
+
+    #[test]
+    fn follows_linked_cels() {
+        let red = [255, 0, 0, 255];
+        let bytes = file(
+            COLOR_DEPTH_RGBA,
+            &[
+                vec![layer_chunk(), raw_cel_chunk(red)],
+                vec![linked_cel_chunk(0)],
+            ],
+        );
+
+        let data = read_aseprite(&bytes).unwrap();
+
+        assert_eq!(data.image.size(), UVec2::new(2, 1));
+        assert_eq!(data.image.data.unwrap(), [red, red].concat());
+    }
+
+    #[test]
+    fn rejects_non_rgba_color_depth() {
+        let mut bytes = STAR.to_vec();
+        // Indexed color
+        bytes[12..14].copy_from_slice(&8u16.to_le_bytes());
+
+        assert!(read_aseprite(&bytes).is_err());
+        assert!(read_aseprite(&file(16, &[vec![layer_chunk()]])).is_err());
+    }
+
+    #[test]
+    fn placeholder_stands_in_for_any_clip() {
+        let placeholder = Aseprite::placeholder(Handle::default(), Handle::default());
+        let mut animation = placeholder.animation("high");
+
+        assert_eq!(animation.current_clip(), "high");
+
+        animation.play("normal");
+
+        assert_eq!(animation.current_clip(), "normal");
+    }
+}
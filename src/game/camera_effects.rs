@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        MAIN_STAGE_HEIGHT, MAIN_STAGE_TRANSFORM, MAIN_STAGE_WIDTH, MainStage,
+        events::{ScreenFlash, Shake, ZoomPunch},
+    },
+    screens::Screen,
+    settings::Settings,
+};
+
+const FLASH_DECAY: f32 = 4.0;
+const MAX_SHAKE_OFFSET: f32 = 6.0;
+const MAX_ZOOM: f32 = 0.25;
+const TRAUMA_DECAY: f32 = 1.5;
+const ZOOM_DECAY: f32 = 0.6;
+
+// Shake, zoom and flash on the main stage. They move the stage itself rather than the camera,
+// so the game UI and transitions stay put. Effects are in real time so they still play out
+// during hit-stop.
+#[derive(Component, Default)]
+pub struct CameraEffects {
+    // Shake grows with the square of trauma, so small hits barely register and big ones stack up
+    trauma: f32,
+    zoom: f32,
+}
+
+impl CameraEffects {
+    fn apply(
+        camera_effects_query: Query<(&CameraEffects, &mut Transform), With<MainStage>>,
+        settings: Res<Settings>,
+    ) {
+        let mut rng = rand::rng();
+
+        for (camera_effects, mut transform) in camera_effects_query {
+            let (shake, zoom) = if settings.reduced_motion {
+                (0.0, 0.0)
+            } else {
+                (
+                    MAX_SHAKE_OFFSET * camera_effects.trauma.powi(2),
+                    camera_effects.zoom,
+                )
+            };
+
+            let offset = Vec2::new(rng.random_range(-1.0..=1.0), rng.random_range(-1.0..=1.0));
+
+            transform.translation = MAIN_STAGE_TRANSFORM.translation + (offset * shake).extend(0.0);
+            transform.scale = Vec3::splat(1.0 + zoom);
+        }
+    }
+
+    fn decay(camera_effects_query: Query<&mut CameraEffects>, time: Res<Time>) {
+        for mut camera_effects in camera_effects_query {
+            camera_effects.trauma =
+                (camera_effects.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+            camera_effects.zoom = (camera_effects.zoom - ZOOM_DECAY * time.delta_secs()).max(0.0);
+        }
+    }
+}
+
+// Full-stage overlay for color flashes, fading out from the flash's alpha
+#[derive(Component)]
+#[require(Sprite, Transform)]
+struct Flash;
+
+impl Flash {
+    fn added(mut commands: Commands, main_stage_query: Query<Entity, Added<CameraEffects>>) {
+        for entity in main_stage_query {
+            commands.entity(entity).with_child((
+                Name::new("Flash"),
+                Flash,
+                Sprite::from_color(Color::NONE, Vec2::new(MAIN_STAGE_WIDTH, MAIN_STAGE_HEIGHT)),
+                Transform::from_xyz(0.0, 0.0, 90.0),
+            ));
+        }
+    }
+
+    fn decay(flash_query: Query<&mut Sprite, With<Flash>>, time: Res<Time>) {
+        for mut sprite in flash_query {
+            let alpha = (sprite.color.alpha() - FLASH_DECAY * time.delta_secs()).max(0.0);
+
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    // Effects are dropped entirely with reduced motion
+    app.add_observer(
+        |trigger: On<Shake>,
+         camera_effects_query: Query<&mut CameraEffects>,
+         settings: Res<Settings>| {
+            if settings.reduced_motion {
+                return;
+            }
+
+            for mut camera_effects in camera_effects_query {
+                camera_effects.trauma = (camera_effects.trauma + trigger.0).min(1.0);
+            }
+        },
+    );
+    app.add_observer(
+        |trigger: On<ZoomPunch>,
+         camera_effects_query: Query<&mut CameraEffects>,
+         settings: Res<Settings>| {
+            if settings.reduced_motion {
+                return;
+            }
+
+            // Punches don't stack, the biggest one wins
+            for mut camera_effects in camera_effects_query {
+                camera_effects.zoom = camera_effects.zoom.max(trigger.0).min(MAX_ZOOM);
+            }
+        },
+    );
+    app.add_observer(
+        |trigger: On<ScreenFlash>,
+         flash_query: Query<&mut Sprite, With<Flash>>,
+         settings: Res<Settings>| {
+            if settings.reduced_motion {
+                return;
+            }
+
+            for mut sprite in flash_query {
+                sprite.color = trigger.0;
+            }
+        },
+    );
+
+    app.add_systems(
+        Update,
+        (
+            Flash::added.in_set(AppSystems::Update),
+            (
+                (CameraEffects::decay, Flash::decay).in_set(AppSystems::TickTimers),
+                CameraEffects::apply.in_set(AppSystems::Update),
+            )
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
+use crate::game::ui::Fever;
+
 #[derive(Debug, Event)]
 // Minigame is over (transition to results)
 pub struct MinigameFinished(pub bool);
@@ -12,6 +16,26 @@ pub struct MinigameSpawned;
 // Minigame is ready to be played
 pub struct MinigameStart;
 
+#[derive(Debug, Event)]
+// Briefly freeze gameplay time for impact (collisions, catches)
+pub struct HitStop(pub Duration);
+
+#[derive(Debug, Event)]
+// Add trauma (0 to 1) to shake the main stage
+pub struct Shake(pub f32);
+
+#[derive(Debug, Event)]
+// Briefly zoom the main stage in by the given fraction
+pub struct ZoomPunch(pub f32);
+
+#[derive(Debug, Event)]
+// Flash the main stage with a color, fading out from the color's alpha
+pub struct ScreenFlash(pub Color);
+
+#[derive(Debug, Event)]
+// A win took extra fever off for a streak or a quick finish
+pub struct FeverBonus(pub Fever);
+
 #[derive(Debug, Event)]
 // Kicks of transition to minigame
 pub struct NewMinigame;
@@ -30,3 +54,15 @@ pub struct SpawnMinigame;
 #[derive(Debug, Event)]
 // Results are shown
 pub struct SpawnResults(pub bool);
+
+#[derive(Debug, Event)]
+// Tear down the finished run and start a new one
+pub struct RestartRun;
+
+#[derive(Debug, Event)]
+// Run is over (quit or restarted), so reset all run state
+pub struct RunEnded;
+
+#[derive(Debug, Event)]
+// A new run has been spawned with the given seed
+pub struct RunStarted(pub u64);
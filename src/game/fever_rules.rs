@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::ui::Fever;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum FeverPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl FeverPreset {
+    /// String key for the preset's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Self::Easy => "difficulty.easy",
+            Self::Normal => "difficulty.normal",
+            Self::Hard => "difficulty.hard",
+        }
+    }
+
+    pub fn rules(self) -> FeverRules {
+        let normal = FeverRules {
+            boss_every: 5,
+            boss_fever: Fever::from_tenths(400),
+            boss_min_gap: 2,
+            game_over: Fever::from_tenths(410),
+            high: Fever::from_tenths(400),
+            low: Fever::from_tenths(380),
+            no_fever: Fever::from_tenths(370),
+            preset: self,
+            starting: Fever::from_tenths(390),
+            step_on_boss_loss: Fever::from_tenths(10),
+            step_on_boss_win: Fever::from_tenths(10),
+            step_on_loss: Fever::from_tenths(5),
+            step_on_win: Fever::from_tenths(5),
+            streak_bonus: Fever::from_tenths(1),
+            streak_length: 3,
+            time_bonus: Fever::from_tenths(1),
+            time_bonus_threshold: 0.5,
+            warning: Fever::from_tenths(405),
+        };
+
+        match self {
+            Self::Easy => FeverRules {
+                boss_every: 6,
+                step_on_boss_loss: Fever::from_tenths(6),
+                starting: Fever::from_tenths(385),
+                step_on_loss: Fever::from_tenths(3),
+                streak_bonus: Fever::from_tenths(2),
+                time_bonus: Fever::from_tenths(2),
+                time_bonus_threshold: 0.4,
+                ..normal
+            },
+            Self::Normal => normal,
+            Self::Hard => FeverRules {
+                boss_every: 4,
+                step_on_boss_win: Fever::from_tenths(6),
+                starting: Fever::from_tenths(395),
+                step_on_win: Fever::from_tenths(3),
+                time_bonus_threshold: 0.6,
+                ..normal
+            },
+        }
+    }
+}
+
+// How the fever behaves over a run. Chosen from a preset before the run starts.
+#[derive(Clone, Debug, Resource)]
+pub struct FeverRules {
+    // A boss minigame comes up every `boss_every` rounds, or sooner once the fever reaches
+    // `boss_fever`, but never within `boss_min_gap` rounds of the previous one. Zero disables them.
+    pub boss_every: usize,
+    pub boss_fever: Fever,
+    pub boss_min_gap: usize,
+    // Reaching this loses the run
+    pub game_over: Fever,
+    // Thresholds for how sick the player and thermometer look
+    pub high: Fever,
+    pub low: Fever,
+    // Reaching this wins the run
+    pub no_fever: Fever,
+    pub preset: FeverPreset,
+    pub starting: Fever,
+    pub step_on_boss_loss: Fever,
+    pub step_on_boss_win: Fever,
+    pub step_on_loss: Fever,
+    pub step_on_win: Fever,
+    // Extra fever taken off for every win once a streak reaches `streak_length`
+    pub streak_bonus: Fever,
+    pub streak_length: usize,
+    // Extra fever taken off for a win with at least `time_bonus_threshold` of the timer left
+    pub time_bonus: Fever,
+    pub time_bonus_threshold: f32,
+    // The thermometer flashes from here on
+    pub warning: Fever,
+}
+
+impl Default for FeverRules {
+    fn default() -> Self {
+        FeverPreset::default().rules()
+    }
+}
+
+impl FeverRules {
+    // Whether the next round should be a boss, given the rounds played since the last one
+    pub fn is_boss_due(&self, rounds_since_boss: usize, reading: Fever) -> bool {
+        if self.boss_every == 0 {
+            return false;
+        }
+
+        rounds_since_boss + 1 >= self.boss_every
+            || (reading >= self.boss_fever && rounds_since_boss >= self.boss_min_gap)
+    }
+
+    // Extra fever a win takes off on top of `step_on_win`, given the current win streak and the
+    // share of the timer left
+    pub fn win_bonus(&self, streak: usize, time_left: f32) -> Fever {
+        let mut bonus = Fever::default();
+
+        if self.streak_length > 0 && streak >= self.streak_length {
+            bonus = bonus + self.streak_bonus;
+        }
+
+        if time_left >= self.time_bonus_threshold {
+            bonus = bonus + self.time_bonus;
+        }
+
+        bonus
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FeverRules>();
+}
@@ -1,13 +1,7 @@
 use bevy::prelude::*;
-use bevy_asset_loader::{
-    asset_collection::AssetCollection,
-    loading_state::{
-        LoadingStateAppExt,
-        config::{ConfigureLoadingState, LoadingStateConfig},
-    },
-};
+use bevy_asset_loader::asset_collection::AssetCollection;
 
-use crate::screens::Screen;
+use crate::{asset_tracking::LoadCollection, game::aseprite::Aseprite};
 
 #[derive(AssetCollection, Resource)]
 pub struct GameAssets {
@@ -17,14 +11,10 @@ pub struct GameAssets {
     pub interlude_background: Handle<Image>,
     #[asset(path = "images/lose_screen.png")]
     pub lose_screen: Handle<Image>,
-    #[asset(path = "images/interlude_player.png")]
-    pub player: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 128, tile_size_y = 80, columns = 3, rows = 1))]
-    pub player_layout: Handle<TextureAtlasLayout>,
-    #[asset(path = "images/thermometer.png")]
-    pub thermometer: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 64, tile_size_y = 32, columns = 2, rows = 2))]
-    pub thermometer_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "images/interlude_player.aseprite")]
+    pub player: Handle<Aseprite>,
+    #[asset(path = "images/thermometer.aseprite")]
+    pub thermometer: Handle<Aseprite>,
     #[asset(path = "images/thermometer_numbers.png")]
     pub thermometer_numbers: Handle<Image>,
     #[asset(texture_atlas_layout(tile_size_x = 8, tile_size_y = 16, columns = 10, rows = 1))]
@@ -36,7 +26,5 @@ pub struct GameAssets {
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<GameAssets>(),
-    );
+    app.load_collection::<GameAssets>();
 }
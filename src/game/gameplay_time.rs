@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeSystems};
+
+use crate::{Pause, game::events::HitStop, settings::Settings};
+
+const SLOW_MOTION_SPEED: f32 = 0.6;
+
+// Time context for everything that happens during a run. Unlike the default `Time`, it stops
+// while the game is paused, so gameplay clocks resume exactly where they left off. It can also
+// be frozen briefly with `HitStop` and slowed down with the slow-motion setting or the assist
+// game speed.
+#[derive(Debug, Default)]
+pub struct Gameplay {
+    hit_stop: Duration,
+    paused: bool,
+}
+
+fn advance_gameplay_time(
+    mut gameplay_time: ResMut<Time<Gameplay>>,
+    settings: Res<Settings>,
+    virtual_time: Res<Time<Virtual>>,
+) {
+    let context = gameplay_time.context_mut();
+
+    let mut delta = if context.paused {
+        Duration::ZERO
+    } else {
+        virtual_time.delta()
+    };
+
+    // Hit-stop eats into real time before any of it reaches gameplay
+    let stopped = context.hit_stop.min(delta);
+    context.hit_stop -= stopped;
+    delta -= stopped;
+
+    if settings.slow_motion {
+        delta = delta.mul_f32(SLOW_MOTION_SPEED);
+    }
+
+    gameplay_time.advance_by(delta.mul_f32(settings.assist.game_speed_scale()));
+}
+
+fn set_gameplay_time_paused(paused: bool) -> impl Fn(ResMut<Time<Gameplay>>) {
+    move |mut gameplay_time: ResMut<Time<Gameplay>>| {
+        gameplay_time.context_mut().paused = paused;
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Time<Gameplay>>();
+
+    app.add_observer(
+        |trigger: On<HitStop>, mut gameplay_time: ResMut<Time<Gameplay>>| {
+            let context = gameplay_time.context_mut();
+
+            // Overlapping hit-stops don't stack
+            context.hit_stop = context.hit_stop.max(trigger.0);
+        },
+    );
+
+    app.add_systems(First, advance_gameplay_time.after(TimeSystems));
+    app.add_systems(OnEnter(Pause(true)), set_gameplay_time_paused(true));
+    app.add_systems(OnExit(Pause(true)), set_gameplay_time_paused(false));
+}
@@ -0,0 +1,88 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        events::ResultsSpawned,
+        fever_rules::{FeverPreset, FeverRules},
+        run_history::RunHistory,
+    },
+    storage,
+};
+
+const STORAGE_NAME: &str = "high_scores";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HighScore {
+    pub best_streak: usize,
+    // Shortest winning run
+    pub fastest_cure: Option<Duration>,
+}
+
+impl HighScore {
+    fn record(&mut self, run_history: &RunHistory) {
+        self.best_streak = self.best_streak.max(run_history.best_streak());
+
+        if run_history.has_won {
+            let total_time = run_history.total_time();
+
+            self.fastest_cure = Some(
+                self.fastest_cure
+                    .map_or(total_time, |fastest_cure| fastest_cure.min(total_time)),
+            );
+        }
+    }
+}
+
+// Best results per difficulty, saved between sessions. Runs played with assist are kept apart.
+#[derive(Debug, Default, Deserialize, Resource, Serialize)]
+#[serde(default)]
+pub struct HighScores {
+    assisted: HashMap<FeverPreset, HighScore>,
+    standard: HashMap<FeverPreset, HighScore>,
+}
+
+impl HighScores {
+    pub fn get(&self, preset: FeverPreset, is_assisted: bool) -> Option<&HighScore> {
+        if is_assisted {
+            self.assisted.get(&preset)
+        } else {
+            self.standard.get(&preset)
+        }
+    }
+
+    fn record(&mut self, preset: FeverPreset, run_history: &RunHistory) {
+        let high_scores = if run_history.is_assisted {
+            &mut self.assisted
+        } else {
+            &mut self.standard
+        };
+
+        high_scores.entry(preset).or_default().record(run_history);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(storage::load::<HighScores>(STORAGE_NAME).unwrap_or_default());
+
+    app.add_observer(
+        |trigger: On<ResultsSpawned>,
+         fever_rules: Res<FeverRules>,
+         mut high_scores: ResMut<HighScores>,
+         run_history: Res<RunHistory>| {
+            // Only finished runs count
+            if !trigger.0 {
+                return;
+            }
+
+            high_scores.record(fever_rules.preset, &run_history);
+
+            if let Err(error) = storage::save(STORAGE_NAME, &*high_scores) {
+                warn!("Failed to save high scores: {error}");
+            }
+        },
+    );
+}
@@ -1,11 +1,18 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
-use rand::seq::IndexedRandom;
+use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
 
 use crate::{
     AppSystems, PausableSystems,
-    game::{events::NewMinigame, game_state::GameState, minigames::MINIGAME_KEYS},
+    game::{
+        events::{NewMinigame, RunEnded, RunStarted},
+        fever_rules::FeverRules,
+        game_state::GameState,
+        gameplay_time::Gameplay,
+        minigames::{BOSS_KEYS, DisabledMinigames, MINIGAME_KEYS},
+        run_history::RunHistory,
+    },
     screens::Screen,
 };
 
@@ -14,6 +21,9 @@ const WAIT_TIME: u64 = 3000;
 #[derive(Resource)]
 pub struct MinigameManager {
     pub current_minigame_key: Option<&'static str>,
+    // Chosen as soon as the interlude starts, so the player sprite can telegraph a boss
+    pub next_minigame_key: Option<&'static str>,
+    pub rng: StdRng,
     pub wait_timer: Timer,
 }
 
@@ -21,6 +31,8 @@ impl Default for MinigameManager {
     fn default() -> Self {
         Self {
             current_minigame_key: None,
+            next_minigame_key: None,
+            rng: StdRng::from_os_rng(),
             wait_timer: Timer::new(Duration::from_millis(WAIT_TIME), TimerMode::Once),
         }
     }
@@ -29,15 +41,50 @@ impl Default for MinigameManager {
 impl MinigameManager {
     fn tick(
         mut commands: Commands,
+        disabled_minigames: Res<DisabledMinigames>,
+        fever_rules: Res<FeverRules>,
         mut minigame_manager: ResMut<MinigameManager>,
-        time: Res<Time>,
+        run_history: Res<RunHistory>,
+        time: Res<Time<Gameplay>>,
     ) {
+        if minigame_manager.next_minigame_key.is_none() {
+            let reading = run_history
+                .fever_readings
+                .last()
+                .copied()
+                .unwrap_or(fever_rules.starting);
+            let available = |keys: &[&'static str]| {
+                keys.iter()
+                    .copied()
+                    .filter(|key| !disabled_minigames.0.contains(key))
+                    .collect::<Vec<_>>()
+            };
+
+            let mut minigame_keys =
+                if fever_rules.is_boss_due(run_history.rounds_since_boss(), reading) {
+                    available(&BOSS_KEYS)
+                } else {
+                    Vec::new()
+                };
+
+            // No boss to play means a regular round instead
+            if minigame_keys.is_empty() {
+                minigame_keys = available(&MINIGAME_KEYS);
+            }
+
+            // With every minigame disabled, they're all played with placeholders
+            if minigame_keys.is_empty() {
+                minigame_keys = MINIGAME_KEYS.to_vec();
+            }
+
+            minigame_manager.next_minigame_key =
+                minigame_keys.choose(&mut minigame_manager.rng).copied();
+        }
+
         minigame_manager.wait_timer.tick(time.delta());
 
         if minigame_manager.wait_timer.just_finished() {
-            let mut rng = rand::rng();
-
-            minigame_manager.current_minigame_key = Some(MINIGAME_KEYS.choose(&mut rng).unwrap());
+            minigame_manager.current_minigame_key = minigame_manager.next_minigame_key.take();
             minigame_manager.wait_timer.reset();
             commands.trigger(NewMinigame);
         }
@@ -47,6 +94,18 @@ impl MinigameManager {
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<MinigameManager>();
 
+    app.add_observer(
+        |trigger: On<RunStarted>, mut minigame_manager: ResMut<MinigameManager>| {
+            minigame_manager.rng = StdRng::seed_from_u64(trigger.0);
+        },
+    );
+
+    app.add_observer(
+        |_: On<RunEnded>, mut minigame_manager: ResMut<MinigameManager>| {
+            *minigame_manager = MinigameManager::default();
+        },
+    );
+
     app.add_systems(
         Update,
         MinigameManager::tick
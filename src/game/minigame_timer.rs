@@ -5,34 +5,56 @@ use bevy::prelude::*;
 use crate::{
     AppSystems, PausableSystems,
     game::{
-        events::{MinigameFinished, MinigameStart},
+        events::{MinigameFinished, MinigameStart, RunEnded},
+        gameplay_time::Gameplay,
         minigame_manager::MinigameManager,
         minigames,
     },
     screens::Screen,
+    settings::Settings,
+    theme::prelude::*,
 };
 
-const MINIGAME_TIMER_DURATION: u64 = 5000;
+pub const MINIGAME_TIMER_DURATION: u64 = 5000;
 const MINIGAME_TIMER_HEIGHT: f32 = 16.0;
 const MINIGAME_TIMER_OFFSET: f32 = -32.0;
 const MINIGAME_TIMER_WIDTH: f32 = 56.0;
 
-#[derive(Component)]
+#[derive(Component, Debug, PartialEq)]
 #[require(Sprite, Transform, Visibility)]
 pub struct MinigameTimer {
     minigame_key: Option<&'static str>,
     timer: Timer,
 }
 
+// Idle until a minigame starts
+impl Default for MinigameTimer {
+    fn default() -> Self {
+        let mut timer = Timer::new(
+            Duration::from_millis(MINIGAME_TIMER_DURATION),
+            TimerMode::Once,
+        );
+
+        timer.pause();
+
+        Self {
+            minigame_key: None,
+            timer,
+        }
+    }
+}
+
 impl MinigameTimer {
+    // Share of the timer that was left, kept after the minigame finishes until the next one starts
+    pub fn fraction_remaining(&self) -> f32 {
+        self.timer.fraction_remaining()
+    }
+
     fn render(minigame_timer_query: Query<(&MinigameTimer, &mut Sprite)>) {
         for (minigame_timer, mut sprite) in minigame_timer_query {
             let custom_size = sprite.custom_size.unwrap();
 
-            let percentage_remaining = minigame_timer.timer.remaining().as_secs_f32()
-                / Duration::from_millis(MINIGAME_TIMER_DURATION).as_secs_f32();
-
-            let timer_width = MINIGAME_TIMER_WIDTH * percentage_remaining;
+            let timer_width = MINIGAME_TIMER_WIDTH * minigame_timer.timer.fraction_remaining();
 
             sprite.custom_size = Some(Vec2::new(timer_width, custom_size.y));
         }
@@ -41,7 +63,7 @@ impl MinigameTimer {
     fn tick(
         mut commands: Commands,
         minigame_timer_query: Query<&mut MinigameTimer>,
-        time: Res<Time>,
+        time: Res<Time<Gameplay>>,
     ) {
         for mut minigame_timer in minigame_timer_query {
             let Some(minigame_key) = minigame_timer.minigame_key else {
@@ -59,22 +81,13 @@ impl MinigameTimer {
     }
 
     pub fn new() -> impl Bundle {
-        let mut timer = Timer::new(
-            Duration::from_millis(MINIGAME_TIMER_DURATION),
-            TimerMode::Once,
-        );
-
-        timer.pause();
-
         (
-            MinigameTimer {
-                minigame_key: None,
-                timer,
-            },
+            MinigameTimer::default(),
             Sprite::from_color(
-                Color::srgb_u8(227, 81, 0),
+                Palette::DEFAULT.accent,
                 Vec2::new(MINIGAME_TIMER_WIDTH, MINIGAME_TIMER_HEIGHT),
             ),
+            ThemeColor::Accent,
             Transform::from_xyz(0.0, MINIGAME_TIMER_OFFSET, 0.0),
             Visibility::Hidden,
         )
@@ -89,7 +102,7 @@ pub(super) fn plugin(app: &mut App) {
             let (mut minigame_timer, mut visibility) = minigame_timer_query.single_mut()?;
 
             minigame_timer.minigame_key = None;
-            minigame_timer.timer.reset();
+            minigame_timer.timer.pause();
 
             *visibility = Visibility::Hidden;
 
@@ -97,12 +110,31 @@ pub(super) fn plugin(app: &mut App) {
         },
     );
 
+    app.add_observer(
+        |_: On<RunEnded>, minigame_timer_query: Query<(&mut MinigameTimer, &mut Visibility)>| {
+            for (mut minigame_timer, mut visibility) in minigame_timer_query {
+                *minigame_timer = MinigameTimer::default();
+                *visibility = Visibility::Hidden;
+            }
+        },
+    );
+
     app.add_observer(
         |_: On<MinigameStart>,
          minigame_manager: Res<MinigameManager>,
-         minigame_timer_query: Query<(&mut MinigameTimer, &mut Visibility)>| {
+         minigame_timer_query: Query<(&mut MinigameTimer, &mut Visibility)>,
+         settings: Res<Settings>| {
             for (mut minigame_timer, mut visibility) in minigame_timer_query {
                 minigame_timer.minigame_key = minigame_manager.current_minigame_key;
+
+                if let Some(minigame_key) = minigame_timer.minigame_key {
+                    minigame_timer.timer.set_duration(
+                        minigames::timer_duration(minigame_key)
+                            .mul_f32(settings.assist.timer_length_scale()),
+                    );
+                }
+
+                minigame_timer.timer.reset();
                 minigame_timer.timer.unpause();
                 *visibility = Visibility::Visible;
             }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::{
     math::bounding::{Aabb2d, IntersectsVolume},
     prelude::*,
@@ -6,17 +8,28 @@ use bevy_asset_loader::prelude::*;
 
 use crate::{
     AppSystems, PausableSystems, app_is_loaded,
-    game::{events::MinigameFinished, in_minigame},
-    screens::Screen,
+    asset_tracking::LoadCollection,
+    game::{
+        events::{HitStop, MinigameFinished, ScreenFlash, Shake},
+        gameplay_time::Gameplay,
+        in_minigame,
+        transition::TransitionStyle,
+    },
+    localization::LocalizedText,
 };
 
-pub const MINIGAME_KEY: &'static str = "control";
+pub const INPUT_HINT: &[&str] = &["A"];
+pub const MINIGAME_KEY: &str = "control";
 pub const SHOULD_LOSE_ON_TIMEOUT: bool = false;
+pub const TRANSITION_STYLE: TransitionStyle = TransitionStyle::Slide;
 
 const AGGRESSIVE_BACK_SPEED: f32 = 1000.0;
 const AGGRESSIVE_BOUNDING_BOX: Vec2 = Vec2::new(40.0, 48.0);
 const AGGRESSIVE_FORWARD_ACCELERATION: f32 = 7.0;
 const AGGRESSIVE_FORWARD_SPEED: f32 = 20.0;
+const COLLISION_FLASH: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+const COLLISION_HIT_STOP: u64 = 150;
+const COLLISION_SHAKE: f32 = 0.8;
 const AGGRESSIVE_X: f32 = -40.0;
 const AGGRESSIVE_Y: f32 = -16.0;
 const MOVEABLE_HORIZONTAL_BOUNDRY: f32 = 75.0;
@@ -33,7 +46,10 @@ struct Aggressive {
 }
 
 impl Aggressive {
-    fn acceleration(mut aggressive_query: Query<&mut Aggressive>, time: Res<Time>) -> Result {
+    fn acceleration(
+        mut aggressive_query: Query<&mut Aggressive>,
+        time: Res<Time<Gameplay>>,
+    ) -> Result {
         let mut aggressive = aggressive_query.single_mut()?;
 
         aggressive.speed += AGGRESSIVE_FORWARD_ACCELERATION * time.delta_secs();
@@ -66,12 +82,12 @@ impl Aggressive {
     fn movement(
         mut aggressive_query: Query<(&mut Aggressive, &mut Transform)>,
         input: Res<ButtonInput<KeyCode>>,
-        time: Res<Time>,
+        time: Res<Time<Gameplay>>,
     ) -> Result {
         let (mut aggressive, mut transform) = aggressive_query.single_mut()?;
 
         if aggressive.moveable {
-            let mut input_direction = Vec2::ZERO.clone();
+            let mut input_direction = Vec2::ZERO;
 
             if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
                 input_direction.x -= 1.0;
@@ -123,7 +139,10 @@ impl Scared {
         }
     }
 
-    fn movement(mut scared_query: Query<&mut Transform, With<Scared>>, time: Res<Time>) -> Result {
+    fn movement(
+        mut scared_query: Query<&mut Transform, With<Scared>>,
+        time: Res<Time<Gameplay>>,
+    ) -> Result {
         let mut transform = scared_query.single_mut()?;
 
         transform.translation += Vec3::X * SCARED_MOVEMENT_SPEED * time.delta_secs();
@@ -173,6 +192,9 @@ fn check_collision(
     let scared_aabb = Aabb2d::new(scared_transform.translation.truncate(), SCARED_BOUNDING_BOX);
 
     if aggressive_aabb.intersects(&scared_aabb) {
+        commands.trigger(HitStop(Duration::from_millis(COLLISION_HIT_STOP)));
+        commands.trigger(Shake(COLLISION_SHAKE));
+        commands.trigger(ScreenFlash(COLLISION_FLASH));
         commands.trigger(MinigameFinished(false));
     }
 
@@ -180,9 +202,7 @@ fn check_collision(
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<ControlAssets>(),
-    );
+    app.load_collection::<ControlAssets>();
 
     app.add_systems(
         Update,
@@ -200,6 +220,10 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+pub(super) fn instruction() -> LocalizedText {
+    LocalizedText::new("minigame.control.instruction")
+}
+
 pub(super) fn spawn_minigame() -> impl Bundle {
     (Stage, children![Aggressive::new(), Scared::new()])
 }
@@ -1,31 +1,147 @@
 pub mod control;
+pub mod nightmare;
 pub mod observe;
 pub mod relieve;
 
-use bevy::prelude::*;
+use std::time::Duration;
 
-pub const MINIGAME_KEYS: [&'static str; 3] = [
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{
+    asset_tracking::LoadingProgress,
+    game::{minigame_timer::MINIGAME_TIMER_DURATION, transition::TransitionStyle},
+    localization::LocalizedText,
+    screens::Screen,
+};
+
+pub const MINIGAME_KEYS: [&str; 3] = [
     control::MINIGAME_KEY,
     observe::MINIGAME_KEY,
     relieve::MINIGAME_KEY,
 ];
 
+// Longer minigames with several phases that come up every few rounds instead of the usual ones
+pub const BOSS_KEYS: [&str; 1] = [nightmare::MINIGAME_KEY];
+
+// Minigames left out of rotation because their assets failed to load
+#[derive(Resource, Default)]
+pub struct DisabledMinigames(pub HashSet<&'static str>);
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((control::plugin, observe::plugin, relieve::plugin));
+    app.add_plugins((
+        control::plugin,
+        nightmare::plugin,
+        observe::plugin,
+        relieve::plugin,
+    ));
+
+    app.init_resource::<DisabledMinigames>();
+
+    // Minigames left out after an earlier failure come back once everything has loaded
+    app.add_systems(
+        OnTransition {
+            exited: Screen::Loading,
+            entered: Screen::Title,
+        },
+        |mut disabled_minigames: ResMut<DisabledMinigames>| {
+            disabled_minigames.0.clear();
+        },
+    );
+}
+
+/// Leaves out the minigames whose assets failed to load, for continuing without them.
+pub fn disable_failed_minigames(
+    asset_server: Res<AssetServer>,
+    mut disabled_minigames: ResMut<DisabledMinigames>,
+    loading_progress: Res<LoadingProgress>,
+) {
+    disabled_minigames.0 = MINIGAME_KEYS
+        .into_iter()
+        .chain(BOSS_KEYS)
+        .filter(|key| has_failed_assets(key, &asset_server, &loading_progress))
+        .collect();
+}
+
+fn has_failed_assets(
+    key: &str,
+    asset_server: &AssetServer,
+    loading_progress: &LoadingProgress,
+) -> bool {
+    match key {
+        control::MINIGAME_KEY | nightmare::MINIGAME_KEY => {
+            loading_progress.has_failed::<control::ControlAssets>(asset_server)
+        }
+        observe::MINIGAME_KEY => {
+            loading_progress.has_failed::<observe::ObserveAssets>(asset_server)
+        }
+        relieve::MINIGAME_KEY => {
+            loading_progress.has_failed::<relieve::RelieveAssets>(asset_server)
+        }
+        _ => false,
+    }
+}
+
+pub(super) fn input_hint(key: &str) -> &'static [&'static str] {
+    match key {
+        control::MINIGAME_KEY => control::INPUT_HINT,
+        nightmare::MINIGAME_KEY => nightmare::INPUT_HINT,
+        observe::MINIGAME_KEY => observe::INPUT_HINT,
+        relieve::MINIGAME_KEY => relieve::INPUT_HINT,
+        _ => &[],
+    }
+}
+
+// Short imperative shown on the transition before the minigame starts
+pub(super) fn instruction(key: &str) -> LocalizedText {
+    match key {
+        control::MINIGAME_KEY => control::instruction(),
+        nightmare::MINIGAME_KEY => nightmare::instruction(),
+        observe::MINIGAME_KEY => observe::instruction(),
+        relieve::MINIGAME_KEY => relieve::instruction(),
+        _ => LocalizedText::new(name_key(key)),
+    }
+}
+
+// String table key for the minigame's name, as listed on the run summary
+pub(super) fn name_key(key: &str) -> String {
+    format!("minigame.{key}.name")
+}
+
+pub(super) fn is_boss(key: &str) -> bool {
+    BOSS_KEYS.contains(&key)
 }
 
 pub(super) fn should_lose_on_timeout(key: &'static str) -> bool {
     match key {
         control::MINIGAME_KEY => control::SHOULD_LOSE_ON_TIMEOUT,
+        nightmare::MINIGAME_KEY => nightmare::SHOULD_LOSE_ON_TIMEOUT,
         observe::MINIGAME_KEY => observe::SHOULD_LOSE_ON_TIMEOUT,
         relieve::MINIGAME_KEY => relieve::SHOULD_LOSE_ON_TIMEOUT,
         _ => true,
     }
 }
 
+pub(super) fn timer_duration(key: &str) -> Duration {
+    Duration::from_millis(match key {
+        nightmare::MINIGAME_KEY => nightmare::TIMER_DURATION,
+        _ => MINIGAME_TIMER_DURATION,
+    })
+}
+
+pub(super) fn transition_style(key: &str) -> TransitionStyle {
+    match key {
+        control::MINIGAME_KEY => control::TRANSITION_STYLE,
+        nightmare::MINIGAME_KEY => nightmare::TRANSITION_STYLE,
+        observe::MINIGAME_KEY => observe::TRANSITION_STYLE,
+        relieve::MINIGAME_KEY => relieve::TRANSITION_STYLE,
+        _ => TransitionStyle::default(),
+    }
+}
+
 pub(super) fn spawn_minigame(key: &str, commands: &mut EntityCommands) {
     match key {
         control::MINIGAME_KEY => commands.insert(control::spawn_minigame()),
+        nightmare::MINIGAME_KEY => commands.insert(nightmare::spawn_minigame()),
         observe::MINIGAME_KEY => commands.insert(observe::spawn_minigame()),
         relieve::MINIGAME_KEY => commands.insert(relieve::spawn_minigame()),
         _ => panic!("No minigame with key: {}", key),
@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use bevy::{
+    math::bounding::{Aabb2d, IntersectsVolume},
+    prelude::*,
+};
+
+use crate::{
+    AppSystems, PausableSystems, app_is_loaded,
+    game::{
+        events::{HitStop, MinigameFinished, MinigameStart, ScreenFlash, Shake, ZoomPunch},
+        gameplay_time::Gameplay,
+        in_minigame,
+        minigames::control::ControlAssets,
+        transition::TransitionStyle,
+    },
+    localization::LocalizedText,
+    settings::Settings,
+};
+
+pub const INPUT_HINT: &[&str] = &["W", "A", "S", "D"];
+pub const MINIGAME_KEY: &str = "nightmare";
+pub const SHOULD_LOSE_ON_TIMEOUT: bool = false;
+pub const TIMER_DURATION: u64 = 12000;
+pub const TRANSITION_STYLE: TransitionStyle = TransitionStyle::Iris;
+
+const COLLISION_FLASH: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+const COLLISION_HIT_STOP: u64 = 150;
+const COLLISION_SHAKE: f32 = 0.8;
+const DASH_COOLDOWN: u64 = 1200;
+const DASH_DURATION: u64 = 400;
+const DASH_SPEED: f32 = 140.0;
+const HORIZONTAL_BOUNDRY: f32 = 80.0;
+const NIGHTMARE_BOUNDING_BOX: Vec2 = Vec2::new(12.0, 16.0);
+const NIGHTMARE_X: f32 = -64.0;
+const NIGHTMARE_Y: f32 = 0.0;
+const PHASE_COUNT: usize = 3;
+const PHASE_HIT_STOP: u64 = 200;
+const PHASE_SHAKE: f32 = 0.5;
+const PHASE_ZOOM: f32 = 0.1;
+const SPRITE_SCALE: f32 = 0.5;
+const SLEEPER_BOUNDING_BOX: Vec2 = Vec2::new(6.0, 16.0);
+const SLEEPER_MOVEMENT_SPEED: f32 = 60.0;
+const SLEEPER_X: f32 = 64.0;
+const SLEEPER_Y: f32 = 0.0;
+const VERTICAL_BOUNDRY: f32 = 48.0;
+
+// Each phase lasts a third of the timer: a slow chase, then dashes, then a fast chase with dashes
+const PHASE_CHASE_SPEEDS: [f32; PHASE_COUNT] = [24.0, 12.0, 36.0];
+const PHASE_DASHES: [bool; PHASE_COUNT] = [false, true, true];
+
+fn clamp_to_stage(translation: Vec3) -> Vec3 {
+    Vec3::new(
+        translation.x.clamp(-HORIZONTAL_BOUNDRY, HORIZONTAL_BOUNDRY),
+        translation.y.clamp(-VERTICAL_BOUNDRY, VERTICAL_BOUNDRY),
+        translation.z,
+    )
+}
+
+#[derive(Component)]
+#[require(Sprite, Transform)]
+struct Nightmare {
+    dash: Option<(Vec2, Timer)>,
+    dash_cooldown: Timer,
+    phase: usize,
+    phase_timer: Timer,
+}
+
+impl Nightmare {
+    fn added(
+        control_assets: Res<ControlAssets>,
+        nightmare_query: Query<&mut Sprite, Added<Nightmare>>,
+    ) {
+        for mut sprite in nightmare_query {
+            sprite.image = control_assets.aggressive.clone();
+        }
+    }
+
+    fn movement(
+        mut nightmare_query: Query<(&mut Nightmare, &mut Sprite, &mut Transform)>,
+        sleeper_query: Query<&Transform, (With<Sleeper>, Without<Nightmare>)>,
+        time: Res<Time<Gameplay>>,
+    ) -> Result {
+        let (mut nightmare, mut sprite, mut transform) = nightmare_query.single_mut()?;
+        let sleeper_transform = sleeper_query.single()?;
+
+        let to_sleeper = (sleeper_transform.translation - transform.translation)
+            .truncate()
+            .normalize_or_zero();
+
+        let velocity = if let Some((direction, dash_timer)) = nightmare.dash.as_mut() {
+            dash_timer.tick(time.delta());
+
+            let velocity = *direction * DASH_SPEED;
+
+            if dash_timer.is_finished() {
+                nightmare.dash = None;
+            }
+
+            velocity
+        } else {
+            if PHASE_DASHES[nightmare.phase] {
+                nightmare.dash_cooldown.tick(time.delta());
+
+                if nightmare.dash_cooldown.just_finished() {
+                    nightmare.dash = Some((
+                        to_sleeper,
+                        Timer::new(Duration::from_millis(DASH_DURATION), TimerMode::Once),
+                    ));
+                }
+            }
+
+            to_sleeper * PHASE_CHASE_SPEEDS[nightmare.phase]
+        };
+
+        if velocity.x != 0.0 {
+            sprite.flip_x = velocity.x < 0.0;
+        }
+
+        transform.translation =
+            clamp_to_stage(transform.translation + velocity.extend(0.0) * time.delta_secs());
+
+        Ok(())
+    }
+
+    fn new() -> impl Bundle {
+        (
+            Nightmare {
+                dash: None,
+                dash_cooldown: Timer::new(
+                    Duration::from_millis(DASH_COOLDOWN),
+                    TimerMode::Repeating,
+                ),
+                phase: 0,
+                phase_timer: Timer::new(
+                    Duration::from_millis(TIMER_DURATION / PHASE_COUNT as u64),
+                    TimerMode::Repeating,
+                ),
+            },
+            Transform::from_xyz(NIGHTMARE_X, NIGHTMARE_Y, 11.0)
+                .with_scale(Vec3::splat(SPRITE_SCALE)),
+        )
+    }
+
+    fn next_phase(
+        mut commands: Commands,
+        mut nightmare_query: Query<&mut Nightmare>,
+        time: Res<Time<Gameplay>>,
+    ) -> Result {
+        let mut nightmare = nightmare_query.single_mut()?;
+
+        nightmare.phase_timer.tick(time.delta());
+
+        if nightmare.phase_timer.just_finished() && nightmare.phase + 1 < PHASE_COUNT {
+            nightmare.phase += 1;
+            nightmare.dash = None;
+            nightmare.dash_cooldown.reset();
+
+            commands.trigger(HitStop(Duration::from_millis(PHASE_HIT_STOP)));
+            commands.trigger(Shake(PHASE_SHAKE));
+            commands.trigger(ZoomPunch(PHASE_ZOOM));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[require(Sprite, Transform)]
+struct Sleeper;
+
+impl Sleeper {
+    fn added(
+        control_assets: Res<ControlAssets>,
+        sleeper_query: Query<&mut Sprite, Added<Sleeper>>,
+    ) {
+        for mut sprite in sleeper_query {
+            sprite.image = control_assets.scared.clone();
+        }
+    }
+
+    fn movement(
+        input: Res<ButtonInput<KeyCode>>,
+        mut sleeper_query: Query<&mut Transform, With<Sleeper>>,
+        time: Res<Time<Gameplay>>,
+    ) -> Result {
+        let mut transform = sleeper_query.single_mut()?;
+
+        let mut input_direction = Vec2::ZERO;
+
+        if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+            input_direction.y += 1.0;
+        }
+
+        if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+            input_direction.x -= 1.0;
+        }
+
+        if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+            input_direction.y -= 1.0;
+        }
+
+        if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+            input_direction.x += 1.0;
+        }
+
+        input_direction = input_direction.normalize_or_zero();
+
+        transform.translation = clamp_to_stage(
+            transform.translation
+                + input_direction.extend(0.0) * SLEEPER_MOVEMENT_SPEED * time.delta_secs(),
+        );
+
+        Ok(())
+    }
+
+    fn new() -> impl Bundle {
+        (
+            Sleeper,
+            Transform::from_xyz(SLEEPER_X, SLEEPER_Y, 10.0).with_scale(Vec3::splat(SPRITE_SCALE)),
+        )
+    }
+}
+
+#[derive(Component)]
+#[require(Sprite, Transform)]
+struct Stage;
+
+impl Stage {
+    fn added(control_assets: Res<ControlAssets>, stage_query: Query<&mut Sprite, Added<Stage>>) {
+        for mut sprite in stage_query {
+            sprite.image = control_assets.background.clone();
+        }
+    }
+}
+
+fn check_collision(
+    mut commands: Commands,
+    nightmare_query: Query<&Transform, With<Nightmare>>,
+    sleeper_query: Query<&Transform, With<Sleeper>>,
+) -> Result {
+    let nightmare_transform = nightmare_query.single()?;
+    let sleeper_transform = sleeper_query.single()?;
+
+    let nightmare_aabb = Aabb2d::new(
+        nightmare_transform.translation.truncate(),
+        NIGHTMARE_BOUNDING_BOX,
+    );
+    let sleeper_aabb = Aabb2d::new(
+        sleeper_transform.translation.truncate(),
+        SLEEPER_BOUNDING_BOX,
+    );
+
+    if nightmare_aabb.intersects(&sleeper_aabb) {
+        commands.trigger(HitStop(Duration::from_millis(COLLISION_HIT_STOP)));
+        commands.trigger(Shake(COLLISION_SHAKE));
+        commands.trigger(ScreenFlash(COLLISION_FLASH));
+        commands.trigger(MinigameFinished(false));
+    }
+
+    Ok(())
+}
+
+pub(super) fn plugin(app: &mut App) {
+    // Phases split the boss timer evenly, so they stretch along with it in assist mode
+    app.add_observer(
+        |_: On<MinigameStart>, nightmare_query: Query<&mut Nightmare>, settings: Res<Settings>| {
+            for mut nightmare in nightmare_query {
+                nightmare.phase_timer.set_duration(
+                    Duration::from_millis(TIMER_DURATION / PHASE_COUNT as u64)
+                        .mul_f32(settings.assist.timer_length_scale()),
+                );
+                nightmare.phase_timer.reset();
+            }
+        },
+    );
+
+    app.add_systems(
+        Update,
+        (
+            (Nightmare::added, Sleeper::added, Stage::added).in_set(AppSystems::Update),
+            (
+                Sleeper::movement.in_set(AppSystems::RecordInput),
+                (Nightmare::next_phase, Nightmare::movement, check_collision)
+                    .chain()
+                    .in_set(AppSystems::Update),
+            )
+                .run_if(in_minigame(MINIGAME_KEY))
+                .in_set(PausableSystems),
+        )
+            .run_if(app_is_loaded),
+    );
+}
+
+pub(super) fn instruction() -> LocalizedText {
+    LocalizedText::new("minigame.nightmare.instruction")
+}
+
+pub(super) fn spawn_minigame() -> impl Bundle {
+    (Stage, children![Nightmare::new(), Sleeper::new()])
+}
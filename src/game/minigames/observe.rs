@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{f32::consts::TAU, ops::RangeInclusive, time::Duration};
 
 use bevy::{
     math::bounding::{Aabb2d, IntersectsVolume},
@@ -9,22 +9,40 @@ use rand::Rng;
 
 use crate::{
     AppSystems, PausableSystems, app_is_loaded,
+    asset_tracking::LoadCollection,
     game::{
-        MAIN_STAGE_HEIGHT, MAIN_STAGE_WIDTH, animation::Animation, events::MinigameFinished,
-        game_assets::GameAssets, in_minigame,
+        MAIN_STAGE_HEIGHT, MAIN_STAGE_WIDTH,
+        animation::Animation,
+        aseprite::{Aseprite, DEFAULT_CLIP},
+        events::{HitStop, MinigameFinished, RunEnded, ScreenFlash, ZoomPunch},
+        gameplay_time::Gameplay,
+        in_minigame,
+        particles::ParticleEmitter,
+        transition::TransitionStyle,
     },
-    screens::Screen,
+    localization::LocalizedText,
+    theme::prelude::*,
 };
 
-pub const MINIGAME_KEY: &'static str = "observe";
+pub const INPUT_HINT: &[&str] = &["W", "A", "S", "D"];
+pub const MINIGAME_KEY: &str = "observe";
 pub const SHOULD_LOSE_ON_TIMEOUT: bool = true;
-
+pub const TRANSITION_STYLE: TransitionStyle = TransitionStyle::Wipe;
+
+const CATCH_FLASH: Color = Color::srgba(1.0, 0.95, 0.6, 0.25);
+const CATCH_HIT_STOP: u64 = 60;
+const CATCH_LIFETIME: u64 = 400;
+const CATCH_SPARKLE_COUNT: usize = 6;
+const CATCH_SPARKLE_SCALE: f32 = 0.5;
+const CATCH_SPARKLE_SPEED: RangeInclusive<f32> = 30.0..=60.0;
+const CATCH_ZOOM: f32 = 0.04;
 const COLLECT_AMOUNT: usize = 3;
 const STAR_LIFETIME: u64 = 2000;
 
 // Animation
-const GALILEO_FRAMES: [usize; 2] = [0, 1];
-const STAR_FRAMES: [usize; 2] = [0, 1];
+const GALILEO_CLIP_IDLE: &str = "idle";
+const GALILEO_CLIP_WALK: &str = "walk";
+const STAR_CLIP: &str = "twinkle";
 
 // Speed
 const GALILEO_MOVEMENT_SPEED: f32 = 75.0;
@@ -46,18 +64,12 @@ impl Score {
     fn new() -> impl Bundle {
         (
             Score,
+            ScaledFont(10.0),
             Text2d::new(format!("0/{}", COLLECT_AMOUNT)),
             Transform::from_xyz(0.0, (MAIN_STAGE_HEIGHT / 2.0) - 10.0, 0.0),
         )
     }
 
-    fn added(game_assets: Res<GameAssets>, score_query: Query<&mut TextFont, Added<Score>>) {
-        for mut text_font in score_query {
-            text_font.font_size = 10.0;
-            text_font.font = game_assets.font.clone();
-        }
-    }
-
     fn render(observe_manager: Res<ObserveManager>, score_query: Query<&mut Text2d, With<Score>>) {
         for mut text in score_query {
             text.0 = format!("{}/{}", observe_manager.collected, COLLECT_AMOUNT);
@@ -89,21 +101,16 @@ struct Star {
 }
 
 impl Star {
-    fn new(observe_assets: Res<ObserveAssets>, origin: Vec2, target: Vec2) -> impl Bundle {
+    fn new(star: &Aseprite, origin: Vec2, target: Vec2) -> impl Bundle {
         let mut transform = Transform::from_translation(origin.extend(0.0));
 
         transform.rotation = Quat::from_rotation_z(Vec2::Y.angle_to(target));
 
         (
-            Animation::looping(&STAR_FRAMES).with_minigame(MINIGAME_KEY),
+            star.animation(STAR_CLIP).with_minigame(MINIGAME_KEY),
             Sprite {
-                image: observe_assets.star.clone(),
-                texture_atlas: Some(TextureAtlas {
-                    layout: observe_assets.star_layout.clone(),
-                    index: STAR_FRAMES[0],
-                }),
                 flip_y: true,
-                ..default()
+                ..star.sprite(STAR_CLIP)
             },
             Self {
                 life_timer: Timer::new(Duration::from_millis(STAR_LIFETIME), TimerMode::Once),
@@ -122,31 +129,19 @@ struct Galileo;
 pub struct ObserveAssets {
     #[asset(path = "images/observe_background.png")]
     pub background: Handle<Image>,
-    #[asset(path = "images/observe_catch.png")]
-    pub catch: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 2, rows = 4))]
-    pub catch_layout: Handle<TextureAtlasLayout>,
-    #[asset(path = "images/observe_galileo.png")]
-    pub galileo: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 32, tile_size_y = 40, columns = 2, rows = 1))]
-    pub galileo_layout: Handle<TextureAtlasLayout>,
-    #[asset(path = "images/observe_star.png")]
-    pub star: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 8, tile_size_y = 16, columns = 2, rows = 1))]
-    pub star_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "images/observe_catch.aseprite")]
+    pub catch: Handle<Aseprite>,
+    #[asset(path = "images/observe_galileo.aseprite")]
+    pub galileo: Handle<Aseprite>,
+    #[asset(path = "images/observe_star.aseprite")]
+    pub star: Handle<Aseprite>,
 }
 
-#[derive(Resource)]
-struct ObserveManager {
+#[derive(Debug, Default, PartialEq, Resource)]
+pub struct ObserveManager {
     collected: usize,
 }
 
-impl Default for ObserveManager {
-    fn default() -> Self {
-        Self { collected: 0 }
-    }
-}
-
 fn check_win(mut commands: Commands, observe_manager: Res<ObserveManager>) {
     if observe_manager.collected >= COLLECT_AMOUNT {
         commands.trigger(MinigameFinished(true));
@@ -154,25 +149,34 @@ fn check_win(mut commands: Commands, observe_manager: Res<ObserveManager>) {
 }
 
 fn galileo_added(
+    aseprites: Res<Assets<Aseprite>>,
+    mut commands: Commands,
     observe_assets: Res<ObserveAssets>,
-    mut stage_query: Query<&mut Sprite, Added<Galileo>>,
+    mut stage_query: Query<(Entity, &mut Sprite), Added<Galileo>>,
 ) {
-    let Ok(mut sprite) = stage_query.single_mut() else {
+    let Ok((entity, mut sprite)) = stage_query.single_mut() else {
         return;
     };
 
-    sprite.image = observe_assets.galileo.clone();
-    sprite.texture_atlas = Some(TextureAtlas {
-        index: 0,
-        layout: observe_assets.galileo_layout.clone(),
-    });
+    let Some(galileo) = aseprites.get(&observe_assets.galileo) else {
+        return;
+    };
+
+    *sprite = galileo.sprite(GALILEO_CLIP_IDLE);
+    commands.entity(entity).insert(
+        galileo
+            .animation(GALILEO_CLIP_IDLE)
+            .with_minigame(MINIGAME_KEY),
+    );
 }
 
 fn check_observed(
+    aseprites: Res<Assets<Aseprite>>,
     mut commands: Commands,
     galileo_query: Query<&Transform, With<Galileo>>,
+    observe_assets: Res<ObserveAssets>,
     mut observe_manager: ResMut<ObserveManager>,
-    // stage_query: Query<Entity, With<Stage>>,
+    stage_query: Query<Entity, With<Stage>>,
     star_query: Query<(Entity, &Transform), With<Star>>,
 ) {
     let Ok(galileo_transform) = galileo_query.single() else {
@@ -187,9 +191,9 @@ fn check_observed(
         TELESCOPE_BOUNDRY_BOX,
     );
 
-    // let Ok(stage_entity) = stage_query.single() else {
-    //     return;
-    // };
+    let Ok(stage_entity) = stage_query.single() else {
+        return;
+    };
 
     for (star_entity, star_transform) in star_query {
         let star_aabb = Aabb2d::new(star_transform.translation.truncate(), STAR_BOUNDRY_BOX);
@@ -197,22 +201,47 @@ fn check_observed(
         if galileo_aabb.intersects(&star_aabb) {
             commands.entity(star_entity).despawn();
             observe_manager.collected += 1;
-
-            // TODO: Spawn effect
+            commands.trigger(HitStop(Duration::from_millis(CATCH_HIT_STOP)));
+            commands.trigger(ZoomPunch(CATCH_ZOOM));
+            commands.trigger(ScreenFlash(CATCH_FLASH));
+
+            if let (Some(catch), Some(star)) = (
+                aseprites.get(&observe_assets.catch),
+                aseprites.get(&observe_assets.star),
+            ) {
+                commands.entity(stage_entity).with_children(|parent| {
+                    parent.spawn((
+                        ParticleEmitter::new(Sprite::default(), 1, CATCH_LIFETIME)
+                            .with_clip(catch, DEFAULT_CLIP),
+                        Transform::from_translation(star_transform.translation),
+                    ));
+                    parent.spawn((
+                        ParticleEmitter::new(
+                            Sprite::default(),
+                            CATCH_SPARKLE_COUNT,
+                            CATCH_LIFETIME,
+                        )
+                        .with_clip(star, DEFAULT_CLIP)
+                        .with_scale(CATCH_SPARKLE_SCALE)
+                        .with_velocity(Vec2::Y, TAU, CATCH_SPARKLE_SPEED),
+                        Transform::from_translation(star_transform.translation),
+                    ));
+                });
+            }
         }
     }
 }
 
 fn galileo_movement(
-    mut galileo_query: Query<&mut Transform, With<Galileo>>,
+    mut galileo_query: Query<(&mut Animation, &mut Transform), With<Galileo>>,
     input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
+    time: Res<Time<Gameplay>>,
 ) {
-    let Ok(mut transform) = galileo_query.single_mut() else {
+    let Ok((mut animation, mut transform)) = galileo_query.single_mut() else {
         return;
     };
 
-    let mut input_direction = Vec2::ZERO.clone();
+    let mut input_direction = Vec2::ZERO;
 
     if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
         input_direction.y += 1.0;
@@ -229,6 +258,12 @@ fn galileo_movement(
 
     input_direction = input_direction.normalize_or_zero();
 
+    animation.play(if input_direction == Vec2::ZERO {
+        GALILEO_CLIP_IDLE
+    } else {
+        GALILEO_CLIP_WALK
+    });
+
     let mut new_translation = transform.translation
         + input_direction.extend(0.0) * GALILEO_MOVEMENT_SPEED * time.delta_secs();
 
@@ -242,15 +277,20 @@ fn galileo_movement(
 }
 
 fn spawn_stars(
+    aseprites: Res<Assets<Aseprite>>,
     mut commands: Commands,
     observe_assets: Res<ObserveAssets>,
     mut stage_query: Query<(Entity, &mut Stage)>,
-    time: Res<Time>,
+    time: Res<Time<Gameplay>>,
 ) {
     let Ok((stage_entity, mut stage)) = stage_query.single_mut() else {
         return;
     };
 
+    let Some(star) = aseprites.get(&observe_assets.star) else {
+        return;
+    };
+
     let mut rng = rand::rng();
 
     stage.star_timer.tick(time.delta());
@@ -258,7 +298,7 @@ fn spawn_stars(
     if stage.star_timer.just_finished() {
         let star_entity = commands
             .spawn(Star::new(
-                observe_assets,
+                star,
                 Vec2::new(
                     (rng.random::<f32>() * MAIN_STAGE_WIDTH) - (MAIN_STAGE_WIDTH / 2.0),
                     MAIN_STAGE_HEIGHT / 2.0,
@@ -288,7 +328,11 @@ fn stage_added(
     sprite.image = observe_assets.background.clone();
 }
 
-fn star_lifetime(mut commands: Commands, star_query: Query<(Entity, &mut Star)>, time: Res<Time>) {
+fn star_lifetime(
+    mut commands: Commands,
+    star_query: Query<(Entity, &mut Star)>,
+    time: Res<Time<Gameplay>>,
+) {
     for (star_entity, mut star) in star_query {
         star.life_timer.tick(time.delta());
 
@@ -298,7 +342,7 @@ fn star_lifetime(mut commands: Commands, star_query: Query<(Entity, &mut Star)>,
     }
 }
 
-fn star_movement(star_query: Query<&mut Transform, With<Star>>, time: Res<Time>) {
+fn star_movement(star_query: Query<&mut Transform, With<Star>>, time: Res<Time<Gameplay>>) {
     for mut transform in star_query {
         let direction = transform.rotation * Vec3::Y;
 
@@ -306,27 +350,31 @@ fn star_movement(star_query: Query<&mut Transform, With<Star>>, time: Res<Time>)
     }
 }
 
+pub(super) fn instruction() -> LocalizedText {
+    LocalizedText::new("minigame.observe.instruction").with_arg("amount", COLLECT_AMOUNT)
+}
+
 pub fn spawn_minigame() -> impl Bundle {
     (
         Stage::default(),
         children![
-            (
-                Animation::looping(&GALILEO_FRAMES),
-                Galileo,
-                Transform::from_xyz(0.0, -24.0, 5.0)
-            ),
+            (Galileo, Transform::from_xyz(0.0, -24.0, 5.0)),
             Score::new()
         ],
     )
 }
 
 pub fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<ObserveAssets>(),
-    );
+    app.load_collection::<ObserveAssets>();
 
     app.init_resource::<ObserveManager>();
 
+    app.add_observer(
+        |_: On<RunEnded>, mut observe_manager: ResMut<ObserveManager>| {
+            *observe_manager = ObserveManager::default();
+        },
+    );
+
     app.add_systems(
         Update,
         (
@@ -334,13 +382,7 @@ pub fn plugin(app: &mut App) {
             ((
                 galileo_movement.in_set(AppSystems::RecordInput),
                 (spawn_stars, star_lifetime).in_set(AppSystems::TickTimers),
-                (
-                    check_observed,
-                    check_win,
-                    Score::added,
-                    Score::render,
-                    star_movement,
-                )
+                (check_observed, check_win, Score::render, star_movement)
                     .in_set(AppSystems::Update),
             )
                 .run_if(in_minigame(MINIGAME_KEY)),)
@@ -1,4 +1,8 @@
-use std::time::Duration;
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    ops::RangeInclusive,
+    time::Duration,
+};
 
 use bevy::{
     math::bounding::{Aabb2d, IntersectsVolume},
@@ -9,13 +13,34 @@ use rand::Rng;
 
 use crate::{
     AppSystems, PausableSystems, app_is_loaded,
-    game::{events::MinigameFinished, in_minigame},
-    screens::Screen,
+    asset_tracking::LoadCollection,
+    game::{
+        animation::{Animation, AnimationFinished},
+        aseprite::{Aseprite, DEFAULT_CLIP},
+        events::{HitStop, MinigameFinished, ZoomPunch},
+        gameplay_time::Gameplay,
+        in_minigame,
+        particles::ParticleEmitter,
+        transition::TransitionStyle,
+    },
+    localization::LocalizedText,
 };
 
-pub const MINIGAME_KEY: &'static str = "relieve";
+pub const INPUT_HINT: &[&str] = &["A", "D", "SPACE"];
+pub const MINIGAME_KEY: &str = "relieve";
 pub const SHOULD_LOSE_ON_TIMEOUT: bool = true;
-
+pub const TRANSITION_STYLE: TransitionStyle = TransitionStyle::Dissolve;
+
+const BLOOM_HIT_STOP: u64 = 80;
+const BLOOM_PETAL_COLOR: Color = Color::srgb(1.0, 0.6, 0.8);
+const BLOOM_PETAL_COUNT: usize = 10;
+const BLOOM_PETAL_GRAVITY: f32 = 60.0;
+const BLOOM_PETAL_LIFETIME: u64 = 700;
+const BLOOM_PETAL_SIZE: f32 = 2.0;
+const BLOOM_PETAL_SPEED: RangeInclusive<f32> = 20.0..=50.0;
+// From the flower's center up to its head
+const BLOOM_Y_OFFSET: f32 = 20.0;
+const BLOOM_ZOOM: f32 = 0.05;
 const DROP_BOUNDING_BOX: Vec2 = Vec2::new(3.0, 4.0);
 const DROP_LIFETIME: u64 = 2000;
 const DROP_MOVEMENT_SPEED: f32 = 125.0;
@@ -24,10 +49,18 @@ const HAND_MOVEMENT_SPEED: f32 = 75.0;
 const HAND_Y: f32 = 56.0;
 const FLOWER_BOUNDING_BOX: Vec2 = Vec2::new(5.0, 23.0);
 const FLOWER_COUNT: usize = 3;
-const FLOWER_FRAMES: [usize; 3] = [0, 1, 2];
 const FLOWER_HP: usize = 3;
 const FLOWER_Y: f32 = -32.0;
 const MOVEABLE_HORIZONTAL_BOUNDRY: f32 = 75.0;
+const SPLASH_COUNT: usize = 4;
+const SPLASH_GRAVITY: f32 = 200.0;
+const SPLASH_LIFETIME: u64 = 350;
+const SPLASH_SCALE: f32 = 0.5;
+const SPLASH_SPEED: RangeInclusive<f32> = 20.0..=40.0;
+
+// Animation
+const FLOWER_CLIP_BLOOM: &str = "bloom";
+const FLOWER_CLIP_CLOSED: &str = "closed";
 
 #[derive(Component)]
 #[require(Sprite, Transform)]
@@ -37,14 +70,49 @@ struct Flower {
 }
 
 impl Flower {
-    fn added(flower_query: Query<&mut Sprite, Added<Flower>>, relieve_assets: Res<RelieveAssets>) {
-        for mut sprite in flower_query {
-            sprite.image = relieve_assets.flower.clone();
-            sprite.texture_atlas = Some(TextureAtlas {
-                layout: relieve_assets.flower_layout.clone(),
-                index: 0,
-            });
+    fn added(
+        aseprites: Res<Assets<Aseprite>>,
+        mut commands: Commands,
+        flower_query: Query<(Entity, &mut Sprite), Added<Flower>>,
+        relieve_assets: Res<RelieveAssets>,
+    ) {
+        let Some(flower) = aseprites.get(&relieve_assets.flower) else {
+            return;
+        };
+
+        for (entity, mut sprite) in flower_query {
+            // Not scoped to the minigame, so the last bloom plays out during the transition
+            *sprite = flower.sprite(FLOWER_CLIP_CLOSED);
+            commands
+                .entity(entity)
+                .insert(flower.animation(FLOWER_CLIP_CLOSED));
+        }
+    }
+
+    // Petals burst once the flower has fully opened
+    fn burst(
+        trigger: On<AnimationFinished>,
+        mut commands: Commands,
+        flower_query: Query<(&ChildOf, &Transform), With<Flower>>,
+    ) {
+        if trigger.clip != FLOWER_CLIP_BLOOM {
+            return;
         }
+
+        let Ok((child_of, transform)) = flower_query.get(trigger.entity) else {
+            return;
+        };
+
+        commands.entity(child_of.parent()).with_child((
+            ParticleEmitter::new(
+                Sprite::from_color(BLOOM_PETAL_COLOR, Vec2::splat(BLOOM_PETAL_SIZE)),
+                BLOOM_PETAL_COUNT,
+                BLOOM_PETAL_LIFETIME,
+            )
+            .with_gravity(BLOOM_PETAL_GRAVITY)
+            .with_velocity(Vec2::Y, TAU, BLOOM_PETAL_SPEED),
+            Transform::from_translation(transform.translation + Vec3::Y * BLOOM_Y_OFFSET),
+        ));
     }
 
     fn check_hp(flower_query: Query<&mut Flower>) {
@@ -65,16 +133,10 @@ impl Flower {
         )
     }
 
-    fn render(flower_query: Query<(&Flower, &mut Sprite)>) {
-        for (flower, mut sprite) in flower_query {
-            let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
-                continue;
-            };
-
-            texture_atlas.index = if flower.has_bloomed {
-                *FLOWER_FRAMES.last().unwrap()
-            } else {
-                *FLOWER_FRAMES.first().unwrap()
+    fn render(flower_query: Query<(&mut Animation, &Flower)>) {
+        for (mut animation, flower) in flower_query {
+            if flower.has_bloomed && animation.current_clip() == FLOWER_CLIP_CLOSED {
+                animation.play(FLOWER_CLIP_BLOOM);
             }
         }
     }
@@ -93,7 +155,11 @@ impl Drop {
         }
     }
 
-    fn lifetime(mut commands: Commands, drop_query: Query<(&mut Drop, Entity)>, time: Res<Time>) {
+    fn lifetime(
+        mut commands: Commands,
+        drop_query: Query<(&mut Drop, Entity)>,
+        time: Res<Time<Gameplay>>,
+    ) {
         for (mut drop, entity) in drop_query {
             drop.lifetime.tick(time.delta());
 
@@ -103,7 +169,7 @@ impl Drop {
         }
     }
 
-    fn movement(drop_query: Query<&mut Transform, With<Drop>>, time: Res<Time>) {
+    fn movement(drop_query: Query<&mut Transform, With<Drop>>, time: Res<Time<Gameplay>>) {
         for mut transform in drop_query {
             transform.translation += Vec3::NEG_Y * DROP_MOVEMENT_SPEED * time.delta_secs();
         }
@@ -124,26 +190,30 @@ impl Drop {
 struct Hand;
 
 impl Hand {
-    fn added(hand_query: Query<&mut Sprite, Added<Hand>>, relieve_assets: Res<RelieveAssets>) {
+    fn added(
+        aseprites: Res<Assets<Aseprite>>,
+        hand_query: Query<&mut Sprite, Added<Hand>>,
+        relieve_assets: Res<RelieveAssets>,
+    ) {
+        let Some(hand) = aseprites.get(&relieve_assets.hand) else {
+            return;
+        };
+
         for mut sprite in hand_query {
-            sprite.image = relieve_assets.hand.clone();
-            sprite.texture_atlas = Some(TextureAtlas {
-                layout: relieve_assets.hand_layout.clone(),
-                index: 0,
-            });
+            *sprite = hand.sprite(DEFAULT_CLIP);
         }
     }
 
     fn movement(
         mut hand_query: Query<&mut Transform, With<Hand>>,
         input: Res<ButtonInput<KeyCode>>,
-        time: Res<Time>,
+        time: Res<Time<Gameplay>>,
     ) {
         let Ok(mut transform) = hand_query.single_mut() else {
             return;
         };
 
-        let mut input_direction = Vec2::ZERO.clone();
+        let mut input_direction = Vec2::ZERO;
 
         if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
             input_direction.x -= 1.0;
@@ -175,14 +245,10 @@ pub struct RelieveAssets {
     pub background: Handle<Image>,
     #[asset(path = "images/relieve_drop.png")]
     pub drop: Handle<Image>,
-    #[asset(path = "images/relieve_flower.png")]
-    pub flower: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 32, tile_size_y = 64, columns = 3, rows = 1))]
-    pub flower_layout: Handle<TextureAtlasLayout>,
-    #[asset(path = "images/relieve_hand.png")]
-    pub hand: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 32, tile_size_y = 32, columns = 2, rows = 1))]
-    pub hand_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "images/relieve_flower.aseprite")]
+    pub flower: Handle<Aseprite>,
+    #[asset(path = "images/relieve_hand.aseprite")]
+    pub hand: Handle<Aseprite>,
 }
 
 #[derive(Component)]
@@ -225,7 +291,13 @@ fn check_collisions(
     mut commands: Commands,
     drop_query: Query<(Entity, &Transform), With<Drop>>,
     mut flower_query: Query<(&mut Flower, &Transform), With<Flower>>,
+    relieve_assets: Res<RelieveAssets>,
+    stage_query: Query<Entity, With<Stage>>,
 ) {
+    let Ok(stage_entity) = stage_query.single() else {
+        return;
+    };
+
     for (drop_entity, drop_transform) in drop_query {
         let drop_aabb2d = Aabb2d::new(drop_transform.translation.truncate(), DROP_BOUNDING_BOX);
 
@@ -240,6 +312,23 @@ fn check_collisions(
             if drop_aabb2d.intersects(&flower_aabb2d) {
                 commands.entity(drop_entity).despawn();
                 flower.hp -= 1;
+
+                commands.entity(stage_entity).with_child((
+                    ParticleEmitter::new(
+                        Sprite::from_image(relieve_assets.drop.clone()),
+                        SPLASH_COUNT,
+                        SPLASH_LIFETIME,
+                    )
+                    .with_gravity(SPLASH_GRAVITY)
+                    .with_scale(SPLASH_SCALE)
+                    .with_velocity(Vec2::Y, FRAC_PI_2, SPLASH_SPEED),
+                    Transform::from_translation(drop_transform.translation),
+                ));
+
+                if flower.hp == 0 {
+                    commands.trigger(HitStop(Duration::from_millis(BLOOM_HIT_STOP)));
+                    commands.trigger(ZoomPunch(BLOOM_ZOOM));
+                }
             }
         }
     }
@@ -282,21 +371,23 @@ fn spawn_drop(
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<RelieveAssets>(),
-    );
+    app.load_collection::<RelieveAssets>();
+
+    app.add_observer(Flower::burst);
 
     app.add_systems(
         Update,
         (
             (Drop::added, Hand::added, Flower::added, Stage::added).in_set(AppSystems::Update),
             (
+                // The last bloom has to start before the win ends the minigame
                 (
                     check_collisions,
-                    check_win,
                     Flower::check_hp,
                     Flower::render,
+                    check_win,
                 )
+                    .chain()
                     .in_set(AppSystems::Update),
                 (Drop::movement, Hand::movement, spawn_drop).in_set(AppSystems::RecordInput),
                 Drop::lifetime.in_set(AppSystems::TickTimers),
@@ -308,6 +399,10 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+pub(super) fn instruction() -> LocalizedText {
+    LocalizedText::new("minigame.relieve.instruction")
+}
+
 pub(super) fn spawn_minigame() -> impl Bundle {
     (Stage, children![Hand::new()])
 }
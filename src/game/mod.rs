@@ -1,10 +1,18 @@
 mod animation;
+pub mod aseprite;
+mod camera_effects;
 mod events;
+pub mod fever_rules;
 pub mod game_assets;
 mod game_state;
+mod gameplay_time;
+mod high_scores;
 mod minigame_manager;
 mod minigame_timer;
-mod minigames;
+pub mod minigames;
+mod particles;
+mod run_history;
+mod run_summary;
 mod transition;
 mod ui;
 
@@ -12,23 +20,37 @@ use bevy::prelude::*;
 
 use crate::{
     AppSystems, PausableSystems, app_is_loaded,
+    display::PixelSnap,
     game::{
+        animation::Animation,
+        aseprite::Aseprite,
+        camera_effects::CameraEffects,
         events::{
-            MinigameFinished, MinigameSpawned, NewMinigame, ResultsSpawned, SpawnMinigame,
-            SpawnResults,
+            FeverBonus, MinigameFinished, MinigameSpawned, NewMinigame, RestartRun, ResultsSpawned,
+            RunEnded, RunStarted, SpawnMinigame, SpawnResults,
         },
+        fever_rules::FeverRules,
         game_assets::GameAssets,
         game_state::GameState,
+        gameplay_time::Gameplay,
         minigame_manager::MinigameManager,
         minigame_timer::MinigameTimer,
+        run_history::RunHistory,
         transition::Transition,
-        ui::{GAME_OVER_FEVER, NO_FEVER, Thermometer, thermometer},
+        ui::{Combo, Fever, Thermometer, thermometer},
     },
+    localization::LocalizedText,
     screens::Screen,
+    settings::Settings,
+    theme::prelude::*,
 };
 
-const FEVER_INCREMENT: f32 = 0.5;
+const PLAYER_CLIP_HIGH: &str = "high";
+const PLAYER_CLIP_LOW: &str = "low";
+const PLAYER_CLIP_NORMAL: &str = "normal";
 const PLAYER_X: f32 = -18.0;
+const PLAYER_TELEGRAPH_INTERVAL: f32 = 0.15;
+const PLAYER_TELEGRAPH_SHAKE: f32 = 1.0;
 const PLAYER_Y: f32 = 4.0;
 
 // Sizes
@@ -54,13 +76,21 @@ struct Minigame;
 struct Player;
 
 impl Player {
-    fn added(game_assets: Res<GameAssets>, player_query: Query<&mut Sprite, Added<Player>>) {
-        for mut sprite in player_query {
-            sprite.image = game_assets.player.clone();
-            sprite.texture_atlas = Some(TextureAtlas {
-                layout: game_assets.player_layout.clone(),
-                index: 0,
-            });
+    fn added(
+        aseprites: Res<Assets<Aseprite>>,
+        mut commands: Commands,
+        game_assets: Res<GameAssets>,
+        player_query: Query<(Entity, &mut Sprite), Added<Player>>,
+    ) {
+        let Some(aseprite) = aseprites.get(&game_assets.player) else {
+            return;
+        };
+
+        for (entity, mut sprite) in player_query {
+            *sprite = aseprite.sprite(PLAYER_CLIP_NORMAL);
+            commands
+                .entity(entity)
+                .insert(aseprite.animation(PLAYER_CLIP_NORMAL));
         }
     }
 
@@ -69,22 +99,45 @@ impl Player {
     }
 
     fn render(
-        mut player_query: Query<&mut Sprite, With<Player>>,
+        fever_rules: Res<FeverRules>,
+        minigame_manager: Res<MinigameManager>,
+        mut player_query: Query<(&mut Animation, &mut Transform), With<Player>>,
+        settings: Res<Settings>,
         thermometer_query: Query<&Thermometer>,
+        time: Res<Time<Gameplay>>,
     ) {
+        // Flicker between sick and healthy and shiver ahead of a boss. With reduced motion the
+        // player holds still and just looks sick instead.
+        let is_telegraphing = minigame_manager
+            .next_minigame_key
+            .is_some_and(minigames::is_boss);
+        let is_first_half_of_interval =
+            ((time.elapsed_secs() / PLAYER_TELEGRAPH_INTERVAL) as u32).is_multiple_of(2);
+        let shows_sick_frame = settings.reduced_motion || is_first_half_of_interval;
+        let shake_offset =
+            if is_telegraphing && !settings.reduced_motion && is_first_half_of_interval {
+                PLAYER_TELEGRAPH_SHAKE
+            } else {
+                0.0
+            };
+
         for thermometer in thermometer_query {
-            for mut sprite in player_query.iter_mut() {
-                let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
-                    continue;
-                };
+            for (mut animation, mut transform) in player_query.iter_mut() {
+                transform.translation.x = PLAYER_X + shake_offset;
 
-                texture_atlas.index = if thermometer.is_fever_high() {
-                    2
-                } else if thermometer.is_fever_low() {
-                    1
+                animation.play(if is_telegraphing {
+                    if shows_sick_frame {
+                        PLAYER_CLIP_HIGH
+                    } else {
+                        PLAYER_CLIP_NORMAL
+                    }
+                } else if thermometer.is_fever_high(&fever_rules) {
+                    PLAYER_CLIP_HIGH
+                } else if thermometer.is_fever_low(&fever_rules) {
+                    PLAYER_CLIP_LOW
                 } else {
-                    0
-                };
+                    PLAYER_CLIP_NORMAL
+                });
             }
         }
     }
@@ -116,7 +169,19 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(
         |trigger: On<MinigameFinished>,
          mut commands: Commands,
+         game_state: Res<State<GameState>>,
          mut next_state: ResMut<NextState<GameState>>| {
+            // Several systems can finish the same minigame in one frame (e.g. a win and a
+            // timeout), but only the first one should start the transition
+            if game_state.get() != &GameState::Minigame
+                || matches!(
+                    *next_state,
+                    NextState::Pending(_) | NextState::PendingIfNeq(_)
+                )
+            {
+                return;
+            }
+
             commands.spawn(Transition::fade_out(trigger.0));
             next_state.set(GameState::Transitioning);
         },
@@ -141,6 +206,7 @@ pub(super) fn plugin(app: &mut App) {
             let minigame_entity = commands
                 .spawn((
                     Minigame,
+                    PixelSnap,
                     Name::new(format!(
                         "Minigame \"{}\"",
                         minigame_manager.current_minigame_key.unwrap()
@@ -164,11 +230,15 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(
         |trigger: On<SpawnResults>,
          mut commands: Commands,
+         fever_rules: Res<FeverRules>,
          game_assets: Res<GameAssets>,
          main_stage_query: Query<Entity, With<MainStage>>,
          minigame_query: Query<Entity, With<Minigame>>,
          player_query: Query<Entity, With<Player>>,
-         mut thermometer_query: Query<&mut Thermometer>| {
+         mut run_history: ResMut<RunHistory>,
+         settings: Res<Settings>,
+         mut thermometer_query: Query<&mut Thermometer>,
+         time: Res<Time<Gameplay>>| {
             let Ok(main_stage_entity) = main_stage_query.single() else {
                 return;
             };
@@ -181,16 +251,49 @@ pub(super) fn plugin(app: &mut App) {
                 return;
             };
 
-            if trigger.0 {
-                thermometer.reading -= FEVER_INCREMENT;
+            let is_boss = run_history
+                .minigames
+                .last()
+                .is_some_and(|record| minigames::is_boss(record.minigame_key));
+
+            let reading = if trigger.0 {
+                let time_left = run_history
+                    .minigames
+                    .last()
+                    .map_or(0.0, |record| record.time_left);
+                let bonus = fever_rules.win_bonus(run_history.current_streak(), time_left);
+
+                if bonus > Fever::default() {
+                    commands.trigger(FeverBonus(bonus));
+                }
+
+                let step = if is_boss {
+                    fever_rules.step_on_boss_win
+                } else {
+                    fever_rules.step_on_win
+                };
+
+                thermometer.reading() - step - bonus
+            } else if is_boss {
+                thermometer.reading() + fever_rules.step_on_boss_loss
+            } else {
+                thermometer.reading() + fever_rules.step_on_loss
+            };
+
+            // Invincible fever stops just short of game over
+            let reading = if settings.assist.invincible_fever {
+                reading.min(fever_rules.game_over - Fever::from_tenths(1))
             } else {
-                thermometer.reading += FEVER_INCREMENT;
-            }
+                reading
+            };
+
+            thermometer.set_reading(reading);
+            run_history.fever_readings.push(reading);
 
             // Check for game finished
-            let (is_game_finished, has_won) = if thermometer.reading <= NO_FEVER {
+            let (is_game_finished, has_won) = if reading <= fever_rules.no_fever {
                 (true, true)
-            } else if thermometer.reading >= GAME_OVER_FEVER {
+            } else if reading >= fever_rules.game_over {
                 (true, false)
             } else {
                 (false, false)
@@ -215,26 +318,48 @@ pub(super) fn plugin(app: &mut App) {
                     .entity(main_stage_entity)
                     .add_child(finished_screen_entity);
 
-                commands.spawn((
-                    crate::theme::widget::ui_root("", true),
-                    DespawnOnExit(Screen::Gameplay),
-                    children![crate::theme::widget::button(
-                        "To Title",
-                        crate::menus::pause::quit_to_title
-                    ),],
-                    Transform::from_xyz(0.0, 0.0, 11.0),
-                ));
+                run_history.finish(has_won, time.elapsed());
             }
 
             commands.trigger(ResultsSpawned(is_game_finished));
         },
     );
+    app.add_observer(
+        |_: On<RestartRun>,
+         mut commands: Commands,
+         gameplay_query: Query<(Entity, &DespawnOnExit<Screen>)>| {
+            commands.trigger(RunEnded);
+
+            // Everything spawned by `spawn_game` (stage, player, UI, thermometer, timer, and any
+            // transition or minigame still in flight) is scoped to the gameplay screen
+            for (entity, despawn_on_exit) in gameplay_query {
+                if despawn_on_exit.0 == Screen::Gameplay {
+                    commands.entity(entity).despawn();
+                }
+            }
+
+            commands.run_system_cached(spawn_game);
+        },
+    );
+    app.add_observer(
+        |_: On<RunEnded>, mut next_state: ResMut<NextState<GameState>>| {
+            next_state.set(GameState::default());
+        },
+    );
     app.add_plugins((
         animation::plugin,
+        aseprite::plugin,
+        camera_effects::plugin,
+        fever_rules::plugin,
         game_assets::plugin,
+        gameplay_time::plugin,
+        high_scores::plugin,
         minigame_manager::plugin,
         minigame_timer::plugin,
         minigames::plugin,
+        particles::plugin,
+        run_history::plugin,
+        run_summary::plugin,
         transition::plugin,
         ui::plugin,
     ));
@@ -251,21 +376,29 @@ pub(super) fn plugin(app: &mut App) {
         )
             .run_if(app_is_loaded),
     );
+    app.add_systems(OnExit(Screen::Gameplay), end_run);
+}
+
+fn end_run(mut commands: Commands) {
+    commands.trigger(RunEnded);
 }
 
 pub fn spawn_game(
     mut commands: Commands,
+    fever_rules: Res<FeverRules>,
     game_assets: Res<GameAssets>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     // Init game state
     next_state.set(GameState::Interlude);
+    commands.trigger(RunStarted(rand::random()));
 
     // Core game
     commands.spawn((
         DespawnOnExit(Screen::Gameplay),
         MainStage,
-        MAIN_STAGE_TRANSFORM.clone(),
+        MAIN_STAGE_TRANSFORM,
+        CameraEffects::default(),
         Name::new("Game"),
         Sprite::from_image(game_assets.interlude_background.clone()),
         Visibility::default(),
@@ -273,13 +406,9 @@ pub fn spawn_game(
     ));
 
     // Game UI
-    let ui_font = TextFont {
-        font: game_assets.font.clone(),
-        font_size: 8.0,
-        ..default()
-    };
+    let ui_font = ScaledFont(8.0);
     let ui_text_layout = TextLayout::new_with_justify(Justify::Center);
-    let ui_text_background = TextBackgroundColor::BLACK;
+    let ui_text_background = (TextBackgroundColor::BLACK, ThemeColor::TextBackground);
 
     commands.spawn((
         DespawnOnExit(Screen::Gameplay),
@@ -288,31 +417,234 @@ pub fn spawn_game(
         Transform::from_xyz(UI_WIDTH / 2.0, UI_HEIGHT / 2.0, 50.0),
         children![
             (
-                Text2d::new("TIMER"),
+                Text2d::default(),
+                LocalizedText::new("ui.timer"),
                 Transform::from_xyz(0.0, (UI_HEIGHT / 2.0) - 16.0, 1.0)
                     .with_scale(Vec3::splat(0.5)),
-                ui_font.clone(),
-                ui_text_background.clone(),
-                ui_text_layout.clone(),
+                ui_font,
+                ui_text_background,
+                ui_text_layout,
                 children![MinigameTimer::new()],
             ),
             (
-                Text2d::new("FEVER"),
+                Text2d::default(),
+                LocalizedText::new("ui.fever"),
                 Transform::from_xyz(0.0, (UI_HEIGHT / 2.0) - 48.0, 1.0)
                     .with_scale(Vec3::splat(0.5)),
-                ui_font.clone(),
-                ui_text_background.clone(),
-                ui_text_layout.clone(),
-                children![thermometer(game_assets)],
+                ui_font,
+                ui_text_background,
+                ui_text_layout,
+                children![thermometer(game_assets, fever_rules.starting)],
             ),
             (
-                Text2d::new("CONTROLS\n\nWASD: Movement\nSPACE: Action"),
+                Text2d::default(),
+                LocalizedText::new("ui.controls"),
                 Transform::from_xyz(0.0, (UI_HEIGHT / 2.0) - 112.0, 1.0)
                     .with_scale(Vec3::splat(0.5)),
-                ui_font.clone(),
-                ui_text_background.clone(),
-                ui_text_layout.clone(),
+                ui_font,
+                ui_text_background,
+                ui_text_layout,
+            ),
+            (
+                Combo::new(Vec3::new(0.0, -(UI_HEIGHT / 2.0) + 12.0, 1.0)),
+                ui_font,
+                ui_text_background,
+                ui_text_layout,
             ),
         ],
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        asset::AssetPlugin,
+        audio::AudioSource,
+        image::{ImagePlugin, TextureAtlasPlugin},
+        input::InputPlugin,
+        state::app::StatesPlugin,
+        time::TimeUpdateStrategy,
+    };
+    use bevy_asset_loader::prelude::*;
+
+    use super::*;
+    use crate::{
+        Pause,
+        asset_tracking::{self, substitute_placeholders},
+        game::{minigame_timer::MinigameTimer, minigames::observe::ObserveManager},
+        localization,
+    };
+
+    const FRAME_TIME: Duration = Duration::from_millis(50);
+    const MAX_FRAMES: usize = 2000;
+
+    // The game without rendering, windows or audio. Asset collections are inserted straight away
+    // instead of going through the loading screen, so sprites may never show up, but every
+    // gameplay clock and state transition still runs.
+    fn app() -> App {
+        let mut app = App::new();
+
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            ImagePlugin::default_nearest(),
+            TextureAtlasPlugin,
+            InputPlugin,
+            StatesPlugin,
+        ));
+        app.init_asset::<AudioSource>();
+        app.init_asset::<Font>();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+
+        app.init_state::<Screen>();
+        app.add_loading_state(LoadingState::new(Screen::Loading).continue_to_state(Screen::Title));
+        app.init_resource::<Settings>();
+        app.init_resource::<Palette>();
+
+        app.add_plugins((asset_tracking::plugin, localization::plugin, plugin));
+
+        app.configure_sets(
+            Update,
+            (
+                AppSystems::TickTimers,
+                AppSystems::RecordInput,
+                AppSystems::Update,
+            )
+                .chain(),
+        );
+        app.init_state::<Pause>();
+        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        app.add_systems(OnEnter(Screen::Gameplay), spawn_game);
+
+        app.world_mut()
+            .run_system_cached(substitute_placeholders)
+            .unwrap();
+
+        app
+    }
+
+    fn start_run(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+        app.update();
+    }
+
+    // Updates until the condition holds, failing the test if it never does
+    fn advance_until(app: &mut App, condition: impl Fn(&mut World) -> bool) {
+        for _ in 0..MAX_FRAMES {
+            if condition(app.world_mut()) {
+                return;
+            }
+
+            app.update();
+        }
+
+        panic!("condition not reached after {MAX_FRAMES} frames");
+    }
+
+    fn gameplay_entity_count(world: &mut World) -> usize {
+        world
+            .query::<&DespawnOnExit<Screen>>()
+            .iter(world)
+            .filter(|despawn_on_exit| despawn_on_exit.0 == Screen::Gameplay)
+            .count()
+    }
+
+    fn assert_minigame_manager_reset(world: &World) {
+        let minigame_manager = world.resource::<MinigameManager>();
+        let default = MinigameManager::default();
+
+        assert_eq!(minigame_manager.current_minigame_key, None);
+        assert_eq!(minigame_manager.next_minigame_key, None);
+        assert_eq!(minigame_manager.wait_timer, default.wait_timer);
+    }
+
+    #[test]
+    fn restart_run_leaves_nothing_behind() {
+        let mut app = app();
+
+        start_run(&mut app);
+        let spawned_count = gameplay_entity_count(app.world_mut());
+
+        // Restart while a minigame and its transition are both still around
+        advance_until(&mut app, |world| {
+            world.query::<&Minigame>().iter(world).count() > 0
+                && world.query::<&Transition>().iter(world).count() > 0
+        });
+        assert!(gameplay_entity_count(app.world_mut()) > spawned_count);
+
+        app.world_mut().trigger(RestartRun);
+        app.world_mut().flush();
+
+        assert_eq!(gameplay_entity_count(app.world_mut()), spawned_count);
+        assert_eq!(
+            app.world_mut()
+                .query::<&Minigame>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+        assert_eq!(
+            app.world_mut()
+                .query::<&Transition>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+        assert_minigame_manager_reset(app.world());
+        assert_eq!(
+            *app.world().resource::<ObserveManager>(),
+            ObserveManager::default()
+        );
+    }
+
+    #[test]
+    fn leaving_gameplay_resets_run_state() {
+        let mut app = app();
+
+        start_run(&mut app);
+
+        // Leave between minigames, once one has been played and the next is on its way
+        advance_until(&mut app, |world| {
+            let minigame_manager = world.resource::<MinigameManager>();
+
+            minigame_manager.current_minigame_key.is_some()
+                && minigame_manager.wait_timer.fraction() >= 0.5
+        });
+        assert_ne!(*app.world().resource::<RunHistory>(), RunHistory::default());
+        assert!(
+            app.world_mut()
+                .query::<&MinigameTimer>()
+                .single(app.world())
+                .is_ok_and(|minigame_timer| *minigame_timer != MinigameTimer::default())
+        );
+
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Title);
+        app.update();
+
+        assert_minigame_manager_reset(app.world());
+        assert_eq!(
+            *app.world().resource::<ObserveManager>(),
+            ObserveManager::default()
+        );
+        assert_eq!(*app.world().resource::<RunHistory>(), RunHistory::default());
+        assert_eq!(
+            app.world().resource::<State<GameState>>().get(),
+            &GameState::default()
+        );
+
+        // The timer goes with the rest of the gameplay screen
+        assert_eq!(
+            app.world_mut()
+                .query::<&MinigameTimer>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{aseprite::Aseprite, gameplay_time::Gameplay},
+    screens::Screen,
+};
+
+// One-shot burst of particles. Particles are spawned as children on the first frame, and the
+// emitter despawns itself (and them) once the longest-lived particle is done, so bursts can be
+// dropped anywhere and forgotten about.
+#[derive(Component, Clone)]
+#[require(Transform, Visibility)]
+pub struct ParticleEmitter {
+    count: usize,
+    direction: Vec2,
+    // Atlas indices stepped through over each particle's life. Empty keeps the sprite's own.
+    frames: Vec<usize>,
+    gravity: f32,
+    lifetime: Timer,
+    scale: f32,
+    speed: RangeInclusive<f32>,
+    // Angle in radians that velocities fan out across, centered on `direction`
+    spread: f32,
+    sprite: Sprite,
+}
+
+impl ParticleEmitter {
+    pub fn new(sprite: Sprite, count: usize, lifetime: u64) -> Self {
+        Self {
+            count,
+            direction: Vec2::Y,
+            frames: Vec::new(),
+            gravity: 0.0,
+            lifetime: Timer::new(Duration::from_millis(lifetime), TimerMode::Once),
+            scale: 1.0,
+            speed: 0.0..=0.0,
+            spread: 0.0,
+            sprite,
+        }
+    }
+
+    // Plays a clip across each particle's life, rather than at the clip's own speed
+    pub fn with_clip(mut self, aseprite: &Aseprite, clip_name: &str) -> Self {
+        self.sprite = aseprite.sprite(clip_name);
+        self.frames = aseprite
+            .clips
+            .get(clip_name)
+            .map(|clip| clip.frames.iter().map(|frame| frame.index).collect())
+            .unwrap_or_default();
+
+        self
+    }
+
+    pub fn with_gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+
+        self
+    }
+
+    pub fn with_velocity(
+        mut self,
+        direction: Vec2,
+        spread: f32,
+        speed: RangeInclusive<f32>,
+    ) -> Self {
+        self.direction = direction.normalize_or(Vec2::Y);
+        self.spread = spread;
+        self.speed = speed;
+
+        self
+    }
+
+    fn emit(
+        mut commands: Commands,
+        emitter_query: Query<(Entity, &ParticleEmitter), Added<ParticleEmitter>>,
+    ) {
+        let mut rng = rand::rng();
+
+        for (entity, emitter) in emitter_query {
+            let base_angle = emitter.direction.to_angle();
+
+            commands.entity(entity).with_children(|parent| {
+                for _ in 0..emitter.count {
+                    let angle =
+                        base_angle + rng.random_range(-emitter.spread / 2.0..=emitter.spread / 2.0);
+                    let speed = rng.random_range(emitter.speed.clone());
+
+                    parent.spawn((
+                        Name::new("Particle"),
+                        Particle {
+                            frames: emitter.frames.clone(),
+                            gravity: emitter.gravity,
+                            lifetime: emitter.lifetime.clone(),
+                            velocity: Vec2::from_angle(angle) * speed,
+                        },
+                        emitter.sprite.clone(),
+                        Transform::from_scale(Vec3::splat(emitter.scale)),
+                    ));
+                }
+            });
+        }
+    }
+
+    fn lifetime(
+        mut commands: Commands,
+        emitter_query: Query<(Entity, &mut ParticleEmitter)>,
+        time: Res<Time<Gameplay>>,
+    ) {
+        for (entity, mut emitter) in emitter_query {
+            emitter.lifetime.tick(time.delta());
+
+            if emitter.lifetime.is_finished() {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(Sprite, Transform)]
+struct Particle {
+    frames: Vec<usize>,
+    gravity: f32,
+    lifetime: Timer,
+    velocity: Vec2,
+}
+
+impl Particle {
+    fn movement(particle_query: Query<(&mut Particle, &mut Transform)>, time: Res<Time<Gameplay>>) {
+        for (mut particle, mut transform) in particle_query {
+            particle.velocity.y -= particle.gravity * time.delta_secs();
+
+            transform.translation += particle.velocity.extend(0.0) * time.delta_secs();
+        }
+    }
+
+    // Steps through the frames and fades out over the particle's life
+    fn render(particle_query: Query<(&Particle, &mut Sprite)>) {
+        for (particle, mut sprite) in particle_query {
+            let progress = particle.lifetime.fraction();
+
+            if !particle.frames.is_empty()
+                && let Some(texture_atlas) = sprite.texture_atlas.as_mut()
+            {
+                let frame = ((progress * particle.frames.len() as f32) as usize)
+                    .min(particle.frames.len() - 1);
+
+                texture_atlas.index = particle.frames[frame];
+            }
+
+            sprite
+                .color
+                .set_alpha(particle.lifetime.fraction_remaining());
+        }
+    }
+
+    fn tick(particle_query: Query<&mut Particle>, time: Res<Time<Gameplay>>) {
+        for mut particle in particle_query {
+            particle.lifetime.tick(time.delta());
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            ParticleEmitter::emit.in_set(AppSystems::Update),
+            (
+                (ParticleEmitter::lifetime, Particle::tick).in_set(AppSystems::TickTimers),
+                (Particle::movement, Particle::render).in_set(AppSystems::Update),
+            )
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        events::{MinigameFinished, MinigameStart, RunEnded, RunStarted},
+        fever_rules::FeverRules,
+        gameplay_time::Gameplay,
+        minigame_manager::MinigameManager,
+        minigame_timer::MinigameTimer,
+        minigames,
+        ui::Fever,
+    },
+    screens::Screen,
+    settings::Settings,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinigameRecord {
+    pub duration: Duration,
+    pub has_won: bool,
+    pub minigame_key: &'static str,
+    // Share of the minigame timer left when it finished
+    pub time_left: f32,
+}
+
+// Everything that happened during the current run, used by the results screen
+#[derive(Debug, Default, PartialEq, Resource)]
+pub struct RunHistory {
+    pub fever_readings: Vec<Fever>,
+    pub finished_at: Option<Duration>,
+    pub has_won: bool,
+    // Whether an assist option was turned on at any point during the run
+    pub is_assisted: bool,
+    pub minigames: Vec<MinigameRecord>,
+    pub seed: u64,
+    pub started_at: Duration,
+    minigame_started_at: Option<Duration>,
+}
+
+impl RunHistory {
+    pub fn new(seed: u64, starting_fever: Fever, started_at: Duration, is_assisted: bool) -> Self {
+        Self {
+            fever_readings: vec![starting_fever],
+            is_assisted,
+            seed,
+            started_at,
+            ..default()
+        }
+    }
+
+    pub fn best_streak(&self) -> usize {
+        let mut best_streak = 0;
+        let mut streak = 0;
+
+        for record in &self.minigames {
+            if record.has_won {
+                streak += 1;
+                best_streak = best_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+
+        best_streak
+    }
+
+    // Wins in a row at the end of the run so far
+    pub fn current_streak(&self) -> usize {
+        self.minigames
+            .iter()
+            .rev()
+            .take_while(|record| record.has_won)
+            .count()
+    }
+
+    // Rounds played since the last boss, or since the start of the run
+    pub fn rounds_since_boss(&self) -> usize {
+        self.minigames
+            .iter()
+            .rev()
+            .take_while(|record| !minigames::is_boss(record.minigame_key))
+            .count()
+    }
+
+    pub fn finish(&mut self, has_won: bool, finished_at: Duration) {
+        self.finished_at = Some(finished_at);
+        self.has_won = has_won;
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.finished_at
+            .unwrap_or_default()
+            .saturating_sub(self.started_at)
+    }
+}
+
+// Turning assist on mid-run flags the whole run, even if it's turned off again
+fn flag_assisted_run(mut run_history: ResMut<RunHistory>, settings: Res<Settings>) {
+    if settings.assist.is_enabled() {
+        run_history.is_assisted = true;
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunHistory>();
+
+    app.add_systems(
+        Update,
+        flag_assisted_run.run_if(in_state(Screen::Gameplay).and(resource_changed::<Settings>)),
+    );
+
+    app.add_observer(
+        |trigger: On<RunStarted>,
+         fever_rules: Res<FeverRules>,
+         mut run_history: ResMut<RunHistory>,
+         settings: Res<Settings>,
+         time: Res<Time<Gameplay>>| {
+            *run_history = RunHistory::new(
+                trigger.0,
+                fever_rules.starting,
+                time.elapsed(),
+                settings.assist.is_enabled(),
+            );
+        },
+    );
+
+    app.add_observer(|_: On<RunEnded>, mut run_history: ResMut<RunHistory>| {
+        *run_history = RunHistory::default();
+    });
+
+    app.add_observer(
+        |_: On<MinigameStart>, mut run_history: ResMut<RunHistory>, time: Res<Time<Gameplay>>| {
+            run_history.minigame_started_at = Some(time.elapsed());
+        },
+    );
+
+    app.add_observer(
+        |trigger: On<MinigameFinished>,
+         minigame_manager: Res<MinigameManager>,
+         minigame_timer_query: Query<&MinigameTimer>,
+         mut run_history: ResMut<RunHistory>,
+         time: Res<Time<Gameplay>>| {
+            // A minigame can finish more than once in the same frame (e.g. a win and a timeout),
+            // so only the first one gets recorded.
+            let Some(minigame_started_at) = run_history.minigame_started_at.take() else {
+                return;
+            };
+
+            let Some(minigame_key) = minigame_manager.current_minigame_key else {
+                return;
+            };
+
+            run_history.minigames.push(MinigameRecord {
+                duration: time.elapsed().saturating_sub(minigame_started_at),
+                has_won: trigger.0,
+                minigame_key,
+                time_left: minigame_timer_query
+                    .single()
+                    .map_or(0.0, MinigameTimer::fraction_remaining),
+            });
+        },
+    );
+}
@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+
+use crate::{
+    game::{
+        events::RestartRun, fever_rules::FeverRules, game_state::GameState,
+        high_scores::HighScores, minigames, run_history::RunHistory, ui::Fever,
+    },
+    localization::LocalizedText,
+    menus::pause::quit_to_title,
+    screens::Screen,
+    settings::Settings,
+    theme::prelude::*,
+};
+
+const FEVER_GRAPH_BAR_WIDTH: f32 = 8.0;
+const FEVER_GRAPH_HEIGHT: f32 = 64.0;
+const SUMMARY_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+fn format_duration(duration: Duration) -> String {
+    let total_tenths = duration.as_millis() / 100;
+
+    format!(
+        "{}:{:02}.{}",
+        total_tenths / 600,
+        (total_tenths / 10) % 60,
+        total_tenths % 10
+    )
+}
+
+fn fever_graph(fever_rules: &FeverRules, run_history: &RunHistory) -> impl Bundle {
+    let bars = run_history
+        .fever_readings
+        .iter()
+        .map(|reading| {
+            let percentage = ((*reading - fever_rules.no_fever).tenths() as f32
+                / (fever_rules.game_over - fever_rules.no_fever).tenths() as f32)
+                .clamp(0.0, 1.0);
+
+            (
+                Name::new("Fever Graph Bar"),
+                Node {
+                    width: px(FEVER_GRAPH_BAR_WIDTH),
+                    // Always show a sliver so a cured reading is still visible
+                    height: percent((percentage * 100.0).max(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Palette::DEFAULT.accent),
+                ThemeColor::Accent,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new("Fever Graph"),
+        Node {
+            align_items: AlignItems::End,
+            column_gap: px(2),
+            height: px(FEVER_GRAPH_HEIGHT),
+            ..default()
+        },
+        Children::spawn(SpawnIter(bars.into_iter())),
+    )
+}
+
+fn minigame_list(run_history: &RunHistory) -> impl Bundle {
+    let rows = run_history
+        .minigames
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            widget::label(
+                LocalizedText::new(if record.has_won {
+                    "summary.minigame_won"
+                } else {
+                    "summary.minigame_lost"
+                })
+                .with_arg("index", index + 1)
+                .with_localized_arg("minigame", minigames::name_key(record.minigame_key))
+                .with_arg("time", format_duration(record.duration)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new("Minigame List"),
+        Node {
+            column_gap: px(30),
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::Center,
+            max_width: px(900),
+            row_gap: px(10),
+            ..default()
+        },
+        Children::spawn(SpawnIter(rows.into_iter())),
+    )
+}
+
+// Final reading, kept in the chosen temperature unit
+#[derive(Component)]
+struct FinalFeverLabel(Fever);
+
+impl FinalFeverLabel {
+    fn render(label_query: Query<(&FinalFeverLabel, &mut LocalizedText)>, settings: Res<Settings>) {
+        for (label, mut localized_text) in label_query {
+            localized_text.set_if_neq(LocalizedText::plain(
+                label.0.format(settings.temperature_unit),
+            ));
+        }
+    }
+}
+
+fn stats_grid(
+    fever_rules: &FeverRules,
+    high_scores: &HighScores,
+    run_history: &RunHistory,
+) -> impl Bundle {
+    let high_score = high_scores
+        .get(fever_rules.preset, run_history.is_assisted)
+        .cloned()
+        .unwrap_or_default();
+
+    let final_fever = run_history
+        .fever_readings
+        .last()
+        .copied()
+        .unwrap_or(fever_rules.starting);
+
+    let content = [
+        [
+            LocalizedText::new("summary.total_time"),
+            LocalizedText::plain(format_duration(run_history.total_time())),
+        ],
+        [
+            LocalizedText::new("summary.best_streak"),
+            LocalizedText::plain(run_history.best_streak().to_string()),
+        ],
+        [
+            LocalizedText::new("summary.seed"),
+            LocalizedText::plain(run_history.seed.to_string()),
+        ],
+        [
+            LocalizedText::new("summary.difficulty"),
+            LocalizedText::new(fever_rules.preset.name_key()),
+        ],
+        [
+            LocalizedText::new("summary.assist"),
+            LocalizedText::new(if run_history.is_assisted {
+                "summary.assist_used"
+            } else {
+                "summary.assist_off"
+            }),
+        ],
+        [
+            LocalizedText::new("summary.record_streak"),
+            LocalizedText::plain(high_score.best_streak.to_string()),
+        ],
+        [
+            LocalizedText::new("summary.record_cure"),
+            LocalizedText::plain(
+                high_score
+                    .fastest_cure
+                    .map_or("-".to_string(), format_duration),
+            ),
+        ],
+    ];
+
+    (
+        Name::new("Stats Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: px(10),
+            column_gap: px(30),
+            grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+            ..default()
+        },
+        Children::spawn((
+            SpawnIter(content.into_iter().flatten().enumerate().map(|(i, text)| {
+                (
+                    widget::label(text),
+                    Node {
+                        justify_self: if i.is_multiple_of(2) {
+                            JustifySelf::End
+                        } else {
+                            JustifySelf::Start
+                        },
+                        ..default()
+                    },
+                )
+            })),
+            Spawn((
+                widget::label("summary.final_fever"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                },
+            )),
+            Spawn((
+                widget::label(LocalizedText::plain("")),
+                FinalFeverLabel(final_fever),
+                Node {
+                    justify_self: JustifySelf::Start,
+                    ..default()
+                },
+            )),
+        )),
+    )
+}
+
+fn play_again(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(RestartRun);
+}
+
+fn spawn_run_summary(
+    mut commands: Commands,
+    fever_rules: Res<FeverRules>,
+    high_scores: Res<HighScores>,
+    run_history: Res<RunHistory>,
+) {
+    commands.spawn((
+        widget::ui_root("Run Summary", true),
+        BackgroundColor(SUMMARY_BACKGROUND),
+        DespawnOnExit(GameState::GameOver),
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            widget::header(if run_history.has_won {
+                "summary.won"
+            } else {
+                "summary.lost"
+            }),
+            minigame_list(&run_history),
+            fever_graph(&fever_rules, &run_history),
+            stats_grid(&fever_rules, &high_scores, &run_history),
+            (
+                Name::new("Run Summary Buttons"),
+                Node {
+                    column_gap: px(30),
+                    ..default()
+                },
+                children![
+                    widget::button("summary.play_again", play_again),
+                    widget::button("menu.to_title", quit_to_title),
+                ],
+            ),
+        ],
+    ));
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::GameOver), spawn_run_summary);
+    app.add_systems(
+        Update,
+        FinalFeverLabel::render
+            .run_if(resource_changed::<Settings>.or(any_match_filter::<Added<FinalFeverLabel>>)),
+    );
+}
@@ -1,27 +1,46 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::update_text2d_layout, text::TextLayoutInfo};
 use bevy_asset_loader::prelude::*;
+use rand::seq::SliceRandom;
 
 use crate::{
     AppSystems, PausableSystems, app_is_loaded,
+    asset_tracking::LoadCollection,
     game::{
         MAIN_STAGE_HEIGHT, MAIN_STAGE_WIDTH,
+        aseprite::{Aseprite, DEFAULT_CLIP},
         events::{
-            InterludeStart, MinigameSpawned, MinigameStart, ResultsSpawned, SpawnMinigame,
-            SpawnResults,
+            InterludeStart, MinigameSpawned, MinigameStart, ResultsSpawned, RunEnded,
+            SpawnMinigame, SpawnResults,
         },
-        game_assets::GameAssets,
         game_state::GameState,
+        gameplay_time::Gameplay,
         minigame_manager::MinigameManager,
+        minigames,
+        run_history::RunHistory,
     },
+    localization::LocalizedText,
     screens::Screen,
+    settings::Settings,
+    theme::prelude::*,
 };
 
 const ANIMATION_SPEED: u64 = 100;
-const FADE_IN_END_FRAME: usize = 14;
-const FADE_IN_PAUSE_FRAME: usize = 7;
-const FADE_IN_START_FRAME: usize = 0;
+// Covered on the first step and uncovered on the second
+const CUT_STEPS: usize = 2;
+// How long the instruction card stays up, shrinking each round down to the minimum
+const CARD_DURATION: u64 = 1500;
+const CARD_DURATION_MIN: u64 = 500;
+const CARD_DURATION_STEP: u64 = 100;
+const KEY_CAP_FONT_SIZE: f32 = 8.0;
+const KEY_CAP_GAP: f32 = 4.0;
+const KEY_CAP_Y: f32 = -52.0;
+// Steps for the tile-based styles, with the stage fully covered halfway through
+const TILE_STEPS: usize = 14;
+const TRANSITION_TEXT_FONT_SIZE: f32 = 20.0;
+const TILE_COLOR: Color = Color::WHITE;
+const TILE_SIZE: f32 = 8.0;
 
 #[derive(Clone)]
 pub enum TransitionState {
@@ -31,11 +50,126 @@ pub enum TransitionState {
     FadeB(bool),
     // Spawn things and show text
     Paused,
+    // Hold the instruction card before finishing the fade-in
+    ShowingCard,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TransitionStyle {
+    // Clouds drawn frame by frame in `dream_transition.aseprite`
+    #[default]
+    Wipe,
+    // A circle closing in on the middle of the stage
+    Iris,
+    // A cover sliding in from the left
+    Slide,
+    // The stage breaking up into pixels
+    Dissolve,
+    // No animation at all, used instead of the others with reduced motion
+    Cut,
+}
+
+impl TransitionStyle {
+    // Cover tiles for the shader-free styles. Each tile has a threshold between 0 and 1, and is
+    // shown once the transition covers more than that much of the stage.
+    fn tiles(self) -> Vec<impl Bundle> {
+        let columns = (MAIN_STAGE_WIDTH / TILE_SIZE).ceil() as usize;
+        let rows = (MAIN_STAGE_HEIGHT / TILE_SIZE).ceil() as usize;
+        let max_distance = Vec2::new(MAIN_STAGE_WIDTH, MAIN_STAGE_HEIGHT).length() / 2.0;
+
+        let mut dissolve_order = (0..columns * rows).collect::<Vec<_>>();
+        dissolve_order.shuffle(&mut rand::rng());
+
+        (0..columns * rows)
+            .map(|tile_index| {
+                let column = tile_index % columns;
+                let row = tile_index / columns;
+                let position = Vec2::new(
+                    (column as f32 + 0.5) * TILE_SIZE - (MAIN_STAGE_WIDTH / 2.0),
+                    (MAIN_STAGE_HEIGHT / 2.0) - (row as f32 + 0.5) * TILE_SIZE,
+                );
+
+                let threshold = match self {
+                    Self::Dissolve => dissolve_order[tile_index] as f32 / (columns * rows) as f32,
+                    Self::Iris => 1.0 - (position.length() / max_distance).min(1.0),
+                    Self::Slide => column as f32 / columns as f32,
+                    Self::Cut | Self::Wipe => 0.0,
+                };
+
+                (
+                    Sprite::from_color(TILE_COLOR, Vec2::splat(TILE_SIZE)),
+                    TransitionTile { threshold },
+                    // Beneath the transition text
+                    Transform::from_translation(position.extend(-1.0)),
+                    Visibility::Hidden,
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Component)]
 pub struct TransitionText;
 
+// One key of the input hint under the instruction card
+#[derive(Component)]
+struct KeyCap;
+
+impl KeyCap {
+    // Placed by `KeyCap::layout` once the text has been measured
+    fn new(key: &str) -> impl Bundle {
+        (
+            KeyCap,
+            Text2d::new(format!(" {} ", key)),
+            TextBackgroundColor(Palette::DEFAULT.text_background),
+            ThemeColor::TextBackground,
+            TextColor(Color::WHITE),
+            ScaledFont(KEY_CAP_FONT_SIZE),
+            Transform::from_xyz(0.0, KEY_CAP_Y, 1.0),
+        )
+    }
+
+    // Key caps side by side, centered under the card. Widths are measured from the laid-out
+    // text, since the readable font isn't monospace and the text size can change.
+    fn layout(
+        mut key_cap_query: Query<(&TextLayoutInfo, &mut Transform), With<KeyCap>>,
+        settings: Res<Settings>,
+        transition_query: Query<&Children, With<Transition>>,
+    ) {
+        let gap = KEY_CAP_GAP * settings.text_scale_factor();
+
+        for children in transition_query {
+            let key_caps = children
+                .iter()
+                .filter(|child| key_cap_query.contains(*child))
+                .collect::<Vec<_>>();
+            let total_width = key_caps
+                .iter()
+                .filter_map(|key_cap| key_cap_query.get(*key_cap).ok())
+                .map(|(layout_info, _)| layout_info.size.x)
+                .sum::<f32>()
+                + gap * key_caps.len().saturating_sub(1) as f32;
+
+            let mut x = -total_width / 2.0;
+
+            for key_cap in key_caps {
+                let Ok((layout_info, mut transform)) = key_cap_query.get_mut(key_cap) else {
+                    continue;
+                };
+
+                let width = layout_info.size.x;
+                transform.translation.x = x + width / 2.0;
+                x += width + gap;
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct TransitionTile {
+    threshold: f32,
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub enum TransitionType {
     FadeIn,
@@ -46,55 +180,94 @@ pub enum TransitionType {
 #[require(Sprite, Transform)]
 pub struct Transition {
     pub state: TransitionState,
+    pub step: usize,
+    pub steps: usize,
+    pub style: TransitionStyle,
     pub timer: Timer,
     pub transition_type: TransitionType,
 }
 
 impl Transition {
     fn added(
-        game_assets: Res<GameAssets>,
+        aseprites: Res<Assets<Aseprite>>,
+        mut commands: Commands,
         minigame_manager: Res<MinigameManager>,
-        mut text_font_query: Query<(&mut Text2d, &mut TextFont)>,
+        settings: Res<Settings>,
+        mut text_query: Query<&mut LocalizedText, With<TransitionText>>,
         transition_assets: Res<TransitionAssets>,
-        transition_query: Query<(&Children, &mut Sprite, &Transition), Added<Transition>>,
+        transition_query: Query<
+            (Entity, &Children, &mut Sprite, &mut Transition),
+            Added<Transition>,
+        >,
     ) {
-        for (children, mut sprite, transition) in transition_query {
-            let start_frame = match &transition.transition_type {
-                TransitionType::FadeIn => FADE_IN_START_FRAME,
-                TransitionType::FadeOut(_) => FADE_IN_END_FRAME,
-            };
+        let Some(transition_aseprite) = aseprites.get(&transition_assets.transition) else {
+            return;
+        };
 
+        for (entity, children, mut sprite, mut transition) in transition_query {
             let Some(child) = children.first() else {
                 continue;
             };
 
-            let Ok((mut text_2d, mut text_font)) = text_font_query.get_mut(*child) else {
+            let Ok(mut localized_text) = text_query.get_mut(*child) else {
                 continue;
             };
 
-            sprite.image = transition_assets.transition.clone();
-            sprite.texture_atlas = Some(TextureAtlas {
-                layout: transition_assets.transition_layout.clone(),
-                index: start_frame,
-            });
-            if transition.transition_type == TransitionType::FadeIn {
-                text_2d.0 = minigame_manager
+            transition.style = if settings.reduced_motion {
+                TransitionStyle::Cut
+            } else {
+                minigame_manager
                     .current_minigame_key
-                    .unwrap_or("minigame")
-                    .to_uppercase();
+                    .map(minigames::transition_style)
+                    .unwrap_or_default()
+            };
+
+            if transition.style == TransitionStyle::Cut {
+                transition.steps = CUT_STEPS;
+            }
+
+            if transition.style == TransitionStyle::Wipe {
+                transition.steps = transition_aseprite
+                    .clips
+                    .get(DEFAULT_CLIP)
+                    .map(|clip| clip.frames.len().saturating_sub(1))
+                    .unwrap_or(TILE_STEPS);
+
+                *sprite = transition_aseprite.sprite(DEFAULT_CLIP);
+                if let Some(texture_atlas) = sprite.texture_atlas.as_mut() {
+                    texture_atlas.index = transition.position();
+                }
+            } else {
+                sprite.color = Color::NONE;
+                commands.entity(entity).with_children(|parent| {
+                    for tile in transition.style.tiles() {
+                        parent.spawn(tile);
+                    }
+                });
+            }
+
+            if transition.transition_type == TransitionType::FadeIn
+                && let Some(minigame_key) = minigame_manager.current_minigame_key
+            {
+                *localized_text = minigames::instruction(minigame_key);
+
+                commands.entity(entity).with_children(|parent| {
+                    for key in minigames::input_hint(minigame_key) {
+                        parent.spawn(KeyCap::new(key));
+                    }
+                });
             }
-            text_font.font = game_assets.font.clone();
         }
     }
 
     fn new(transition_type: TransitionType) -> impl Bundle {
-        let text = match &transition_type {
-            TransitionType::FadeIn => "MINIGAME".to_string(),
+        let text_key = match &transition_type {
+            TransitionType::FadeIn => "transition.minigame",
             TransitionType::FadeOut(has_won) => {
                 if *has_won {
-                    "WIN!\nFEVER\nDOWN".to_string()
+                    "transition.won"
                 } else {
-                    "LOSE!\nFEVER\nUP".to_string()
+                    "transition.lost"
                 }
             }
         };
@@ -107,18 +280,23 @@ impl Transition {
                 100.0,
             ),
             Self {
-                transition_type,
                 state: TransitionState::FadeA,
+                step: 0,
+                steps: TILE_STEPS,
+                style: TransitionStyle::default(),
                 timer: Timer::new(Duration::from_millis(ANIMATION_SPEED), TimerMode::Once),
+                transition_type,
             },
             children![(
-                Text2d::new(text),
+                Text2d::default(),
+                LocalizedText::new(text_key),
                 TextLayout::new_with_justify(Justify::Center),
                 TextShadow {
                     color: Color::WHITE,
                     offset: Vec2::new(5.0, 5.0),
                 },
                 TextColor(Color::BLACK),
+                ScaledFont(TRANSITION_TEXT_FONT_SIZE),
                 TransitionText,
             )],
         )
@@ -131,22 +309,73 @@ impl Transition {
     pub fn fade_out(has_won: bool) -> impl Bundle {
         Self::new(TransitionType::FadeOut(has_won))
     }
+
+    // How much of the stage is covered, rising to 1 at the pause and falling back to 0
+    fn coverage(&self) -> f32 {
+        let pause_step = self.pause_step();
+        let position = self.position();
+
+        if position <= pause_step {
+            position as f32 / pause_step.max(1) as f32
+        } else {
+            self.steps.saturating_sub(position) as f32
+                / self.steps.saturating_sub(pause_step).max(1) as f32
+        }
+    }
+
+    // Halfway through, where the stage is fully covered
+    fn pause_step(&self) -> usize {
+        self.steps / 2
+    }
+
+    // How far along the fade-in animation this is. Fade-outs play it backwards.
+    fn position(&self) -> usize {
+        match self.transition_type {
+            TransitionType::FadeIn => self.step.min(self.steps),
+            TransitionType::FadeOut(_) => self.steps.saturating_sub(self.step),
+        }
+    }
+
+    fn render(
+        mut tile_query: Query<(&ChildOf, &TransitionTile, &mut Visibility)>,
+        mut transition_query: Query<(&mut Sprite, &Transition)>,
+    ) {
+        for (mut sprite, transition) in transition_query.iter_mut() {
+            if transition.style != TransitionStyle::Wipe {
+                continue;
+            }
+
+            if let Some(texture_atlas) = sprite.texture_atlas.as_mut() {
+                texture_atlas.index = transition.position();
+            }
+        }
+
+        for (child_of, tile, mut visibility) in tile_query.iter_mut() {
+            let Ok((_, transition)) = transition_query.get(child_of.parent()) else {
+                continue;
+            };
+
+            *visibility = if transition.coverage() > tile.threshold {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
 }
 
 #[derive(AssetCollection, Resource)]
 pub struct TransitionAssets {
-    #[asset(path = "images/dream_transition.png")]
-    pub transition: Handle<Image>,
-    #[asset(texture_atlas_layout(tile_size_x = 192, tile_size_y = 144, columns = 4, rows = 4))]
-    pub transition_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "images/dream_transition.aseprite")]
+    pub transition: Handle<Aseprite>,
 }
 
 fn transition_animate(
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
-    mut transition_query: Query<(Entity, &mut Sprite, &mut Transition)>,
+    mut transition_query: Query<(Entity, &mut Transition)>,
 ) {
-    let Ok((entity, mut sprite, mut transition)) = transition_query.single_mut() else {
+    let Ok((entity, mut transition)) = transition_query.single_mut() else {
         return;
     };
 
@@ -154,20 +383,12 @@ fn transition_animate(
         return;
     }
 
-    let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
-        return;
-    };
-
-    let (end_frame, next_index) = match transition.transition_type {
-        TransitionType::FadeIn => (FADE_IN_END_FRAME, texture_atlas.index + 1),
-        TransitionType::FadeOut(_) => (FADE_IN_START_FRAME, texture_atlas.index - 1),
-    };
-
     match transition.state.clone() {
         TransitionState::FadeA => {
-            texture_atlas.index = next_index;
+            let pause_step = transition.pause_step();
+            transition.step = (transition.step + 1).min(pause_step);
 
-            if texture_atlas.index == FADE_IN_PAUSE_FRAME {
+            if transition.step == pause_step {
                 transition.state = TransitionState::Paused;
                 match &transition.transition_type {
                     TransitionType::FadeIn => {
@@ -181,10 +402,14 @@ fn transition_animate(
                 transition.timer.reset();
             }
         }
+        TransitionState::ShowingCard => {
+            transition.state = TransitionState::FadeB(false);
+            transition.timer = Timer::new(Duration::from_millis(ANIMATION_SPEED), TimerMode::Once);
+        }
         TransitionState::FadeB(is_game_finished) => {
-            texture_atlas.index = next_index;
+            transition.step = (transition.step + 1).min(transition.steps);
 
-            if texture_atlas.index == end_frame {
+            if transition.step == transition.steps {
                 match &transition.transition_type {
                     TransitionType::FadeIn => {
                         commands.trigger(MinigameStart);
@@ -208,7 +433,7 @@ fn transition_animate(
     }
 }
 
-fn transition_timer(time: Res<Time>, mut transition_query: Query<&mut Transition>) {
+fn transition_timer(time: Res<Time<Gameplay>>, mut transition_query: Query<&mut Transition>) {
     let Ok(mut transition) = transition_query.single_mut() else {
         return;
     };
@@ -217,18 +442,33 @@ fn transition_timer(time: Res<Time>, mut transition_query: Query<&mut Transition
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<TransitionAssets>(),
+    app.load_collection::<TransitionAssets>();
+
+    app.add_observer(
+        |_: On<RunEnded>,
+         mut commands: Commands,
+         transition_query: Query<Entity, With<Transition>>| {
+            for transition_entity in transition_query {
+                commands.entity(transition_entity).despawn();
+            }
+        },
     );
 
     app.add_observer(
-        |_: On<MinigameSpawned>, mut transition_query: Query<&mut Transition>| {
+        |_: On<MinigameSpawned>,
+         run_history: Res<RunHistory>,
+         mut transition_query: Query<&mut Transition>| {
             let Ok(mut transition) = transition_query.single_mut() else {
                 return;
             };
 
-            transition.state = TransitionState::FadeB(false);
-            transition.timer.reset();
+            // Players need less time to read the card the further they get
+            let card_duration = CARD_DURATION
+                .saturating_sub(CARD_DURATION_STEP * run_history.minigames.len() as u64)
+                .max(CARD_DURATION_MIN);
+
+            transition.state = TransitionState::ShowingCard;
+            transition.timer = Timer::new(Duration::from_millis(card_duration), TimerMode::Once);
         },
     );
 
@@ -246,13 +486,22 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            Transition::added,
+            Transition::added.in_set(AppSystems::Update),
             (
                 transition_timer.in_set(AppSystems::TickTimers),
-                transition_animate.in_set(AppSystems::Update),
+                (transition_animate, Transition::render)
+                    .chain()
+                    .in_set(AppSystems::Update),
             )
                 .in_set(PausableSystems),
         )
             .run_if(app_is_loaded),
     );
+
+    app.add_systems(
+        PostUpdate,
+        KeyCap::layout
+            .after(update_text2d_layout)
+            .before(TransformSystems::Propagate),
+    );
 }
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{events::FeverBonus, gameplay_time::Gameplay, run_history::RunHistory, ui::Fever},
+    localization::Locale,
+    screens::Screen,
+    settings::Settings,
+};
+
+const BONUS_DURATION: u64 = 1500;
+const COMBO_MIN_STREAK: usize = 2;
+const COMBO_SCALE: f32 = 0.5;
+const PULSE_DURATION: u64 = 300;
+const PULSE_SCALE: f32 = 0.5;
+
+// Win streak counter in the game UI, which pulses when the streak grows and briefly shows
+// the bonus when a win takes extra fever off
+#[derive(Component)]
+#[require(Text2d, Transform, Visibility)]
+pub struct Combo {
+    bonus: Fever,
+    bonus_timer: Timer,
+    pulse_timer: Timer,
+    streak: usize,
+}
+
+impl Combo {
+    pub fn new(translation: Vec3) -> impl Bundle {
+        (
+            Combo {
+                bonus: Fever::default(),
+                bonus_timer: finished_timer(BONUS_DURATION),
+                pulse_timer: finished_timer(PULSE_DURATION),
+                streak: 0,
+            },
+            Transform::from_translation(translation).with_scale(Vec3::splat(COMBO_SCALE)),
+            Visibility::Hidden,
+        )
+    }
+
+    fn render(
+        combo_query: Query<(&mut Combo, &mut Text2d, &mut Transform, &mut Visibility)>,
+        locale: Res<Locale>,
+        run_history: Res<RunHistory>,
+        settings: Res<Settings>,
+    ) {
+        let streak = run_history.current_streak();
+
+        for (mut combo, mut text, mut transform, mut visibility) in combo_query {
+            if streak > combo.streak {
+                combo.pulse_timer.reset();
+            }
+
+            combo.streak = streak;
+
+            let mut lines = Vec::new();
+
+            if streak >= COMBO_MIN_STREAK {
+                lines.push(locale.format("ui.combo", &[("count", streak.to_string())]));
+            }
+
+            if !combo.bonus_timer.is_finished() {
+                lines.push(locale.format(
+                    "ui.bonus",
+                    &[(
+                        "amount",
+                        combo.bonus.format_change(settings.temperature_unit),
+                    )],
+                ));
+            }
+
+            *visibility = if lines.is_empty() {
+                Visibility::Hidden
+            } else {
+                Visibility::Inherited
+            };
+
+            let text_value = lines.join("\n");
+
+            if text.0 != text_value {
+                text.0 = text_value;
+            }
+
+            let pulse = if settings.reduced_motion {
+                0.0
+            } else {
+                PULSE_SCALE * combo.pulse_timer.fraction_remaining()
+            };
+
+            transform.scale = Vec3::splat(COMBO_SCALE * (1.0 + pulse));
+        }
+    }
+
+    fn tick(combo_query: Query<&mut Combo>, time: Res<Time<Gameplay>>) {
+        for mut combo in combo_query {
+            combo.bonus_timer.tick(time.delta());
+            combo.pulse_timer.tick(time.delta());
+        }
+    }
+}
+
+fn finished_timer(duration: u64) -> Timer {
+    let duration = Duration::from_millis(duration);
+    let mut timer = Timer::new(duration, TimerMode::Once);
+
+    timer.tick(duration);
+
+    timer
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(|trigger: On<FeverBonus>, combo_query: Query<&mut Combo>| {
+        for mut combo in combo_query {
+            combo.bonus = trigger.0;
+            combo.bonus_timer.reset();
+            combo.pulse_timer.reset();
+        }
+    });
+
+    app.add_systems(
+        Update,
+        (
+            Combo::tick
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            Combo::render.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
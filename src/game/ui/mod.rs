@@ -1,9 +1,11 @@
+mod combo;
 mod thermometer;
 
 use bevy::prelude::*;
 
-pub use thermometer::{GAME_OVER_FEVER, NO_FEVER, Thermometer, thermometer};
+pub use combo::Combo;
+pub use thermometer::{Fever, Thermometer, thermometer};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(thermometer::plugin);
+    app.add_plugins((combo::plugin, thermometer::plugin));
 }
@@ -1,28 +1,183 @@
+use std::{
+    ops::{Add, Sub},
+    time::Duration,
+};
+
 use bevy::prelude::*;
 
-use crate::game::game_assets::GameAssets;
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        animation::Animation, aseprite::Aseprite, fever_rules::FeverRules, game_assets::GameAssets,
+        gameplay_time::Gameplay,
+    },
+    screens::Screen,
+    settings::{Settings, TemperatureUnit},
+    theme::prelude::*,
+};
+
+const THERMOMETER_CLIP_HIGH: &str = "high";
+const THERMOMETER_CLIP_LOW: &str = "low";
+const THERMOMETER_CLIP_NORMAL: &str = "normal";
+const TWEEN_DURATION: u64 = 500;
+const WARNING_FLASH_INTERVAL: f32 = 0.25;
+
+// X offsets of each `Digit`, for three-digit readings and for four-digit ones. The decimal point
+// sits between the ones and the tenths, and four digits shift over so the reading stays centered.
+const DIGIT_OFFSETS: [[f32; 4]; 2] = [[-16.0, -9.0, -2.0, 6.0], [-13.0, -6.0, 1.0, 9.0]];
+
+// A temperature in tenths of a degree Celsius, so readings like 38.5 are exact
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Fever(u32);
+
+impl Fever {
+    pub const fn from_tenths(tenths: u32) -> Self {
+        Self(tenths)
+    }
+
+    pub fn tenths(self) -> u32 {
+        self.0
+    }
+
+    // Tenths of a degree in the given unit, rounded to the nearest tenth
+    pub fn tenths_in(self, unit: TemperatureUnit) -> u32 {
+        match unit {
+            TemperatureUnit::Celsius => self.0,
+            TemperatureUnit::Fahrenheit => (self.0 * 9 + 1600 + 2) / 5,
+        }
+    }
+
+    // Hundreds, tens, ones, and tenths digits, e.g. [1, 0, 4, 0] for 104.0
+    pub fn digits(self, unit: TemperatureUnit) -> [usize; 4] {
+        let tenths = self.tenths_in(unit) as usize;
 
-pub const GAME_OVER_FEVER: f32 = 41.0;
-const HIGH_FEVER: f32 = 40.0;
-const STARTING_FEVER: f32 = 39.0;
-const LOW_FEVER: f32 = 38.0;
-pub const NO_FEVER: f32 = 37.0;
+        [
+            (tenths / 1000) % 10,
+            (tenths / 100) % 10,
+            (tenths / 10) % 10,
+            tenths % 10,
+        ]
+    }
+
+    // e.g. "38.5C" or "101.3F"
+    pub fn format(self, unit: TemperatureUnit) -> String {
+        let tenths = self.tenths_in(unit);
+
+        format!("{}.{}{}", tenths / 10, tenths % 10, unit.symbol())
+    }
+
+    // Formats this as a change in temperature rather than a reading, e.g. "0.2C" or "0.4F"
+    pub fn format_change(self, unit: TemperatureUnit) -> String {
+        let tenths = match unit {
+            TemperatureUnit::Celsius => self.0,
+            TemperatureUnit::Fahrenheit => (self.0 * 9 + 2) / 5,
+        };
+
+        format!("{}.{}{}", tenths / 10, tenths % 10, unit.symbol())
+    }
+}
+
+impl Add for Fever {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fever {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
 
 #[derive(Component)]
+#[require(Sprite)]
 pub struct Thermometer {
-    pub reading: f32,
+    // What the thermometer currently shows, catching up with `reading` over `TWEEN_DURATION`
+    displayed: Fever,
+    reading: Fever,
+    tween_from: Fever,
+    tween_timer: Timer,
 }
 
 impl Thermometer {
-    pub fn is_fever_high(&self) -> bool {
-        self.reading >= HIGH_FEVER
+    fn added(
+        aseprites: Res<Assets<Aseprite>>,
+        mut commands: Commands,
+        game_assets: Res<GameAssets>,
+        thermometer_query: Query<(Entity, &mut Sprite), Added<Thermometer>>,
+    ) {
+        let Some(aseprite) = aseprites.get(&game_assets.thermometer) else {
+            return;
+        };
+
+        for (entity, mut sprite) in thermometer_query {
+            *sprite = aseprite.sprite(THERMOMETER_CLIP_NORMAL);
+            commands
+                .entity(entity)
+                .insert(aseprite.animation(THERMOMETER_CLIP_NORMAL));
+        }
+    }
+
+    fn new(reading: Fever) -> Self {
+        let mut tween_timer = Timer::new(Duration::from_millis(TWEEN_DURATION), TimerMode::Once);
+        tween_timer.finish();
+
+        Self {
+            displayed: reading,
+            reading,
+            tween_from: reading,
+            tween_timer,
+        }
+    }
+
+    fn tween(thermometer_query: Query<&mut Thermometer>, time: Res<Time<Gameplay>>) {
+        for mut thermometer in thermometer_query {
+            if thermometer.displayed == thermometer.reading {
+                continue;
+            }
+
+            thermometer.tween_timer.tick(time.delta());
+
+            let from = thermometer.tween_from.tenths() as f32;
+            let to = thermometer.reading.tenths() as f32;
+            let displayed = from + (to - from) * thermometer.tween_timer.fraction();
+
+            thermometer.displayed = Fever::from_tenths(displayed.round() as u32);
+        }
+    }
+
+    pub fn is_fever_high(&self, fever_rules: &FeverRules) -> bool {
+        self.displayed >= fever_rules.high
+    }
+
+    pub fn is_fever_low(&self, fever_rules: &FeverRules) -> bool {
+        self.displayed <= fever_rules.low
+    }
+
+    fn is_fever_warning(&self, fever_rules: &FeverRules) -> bool {
+        self.displayed >= fever_rules.warning
+    }
+
+    pub fn reading(&self) -> Fever {
+        self.reading
     }
-    pub fn is_fever_low(&self) -> bool {
-        self.reading <= LOW_FEVER
+
+    // Change the reading, animating the display from wherever it is now
+    pub fn set_reading(&mut self, reading: Fever) {
+        self.tween_from = self.displayed;
+        self.reading = reading;
+        self.tween_timer.reset();
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Digit {
+    // Only shown when needed, e.g. for Fahrenheit readings of 100 and up
+    Hundreds,
     Tens,
     Ones,
     Tenths,
@@ -32,62 +187,98 @@ pub enum Digit {
 pub struct ThermometerNumber(Digit);
 
 fn render_thermometer(
-    mut thermometer_query: Query<
-        (&Children, &mut Sprite, &Thermometer),
-        Without<ThermometerNumber>,
+    fever_rules: Res<FeverRules>,
+    palette: Res<Palette>,
+    mut thermometer_query: Query<(&mut Animation, &Children, &mut Sprite, &Thermometer)>,
+    mut thermometer_number_query: Query<
+        (
+            &mut Sprite,
+            &ThermometerNumber,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<Thermometer>,
     >,
-    mut thermometer_number_query: Query<(&mut Sprite, &ThermometerNumber), Without<Thermometer>>,
+    settings: Res<Settings>,
+    time: Res<Time<Gameplay>>,
 ) {
-    let Ok((children, mut sprite, thermometer)) = thermometer_query.single_mut() else {
+    let Ok((mut animation, children, mut sprite, thermometer)) = thermometer_query.single_mut()
+    else {
         return;
     };
 
-    let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
-        return;
-    };
+    animation.play(if thermometer.is_fever_high(&fever_rules) {
+        THERMOMETER_CLIP_HIGH
+    } else if thermometer.is_fever_low(&fever_rules) {
+        THERMOMETER_CLIP_LOW
+    } else {
+        THERMOMETER_CLIP_NORMAL
+    });
 
-    if thermometer.is_fever_high() {
-        texture_atlas.index = 2;
-    } else if thermometer.is_fever_low() {
-        texture_atlas.index = 0;
+    // Flash close to game over, unless flashing is turned off
+    let is_flashing = !settings.reduced_motion
+        && thermometer.is_fever_warning(&fever_rules)
+        && ((time.elapsed_secs() / WARNING_FLASH_INTERVAL) as u32).is_multiple_of(2);
+    let color = if is_flashing {
+        palette.accent
     } else {
-        texture_atlas.index = 1;
-    }
+        Color::WHITE
+    };
+
+    sprite.color = color;
+
+    let [hundreds, tens, ones, tenths] = thermometer.displayed.digits(settings.temperature_unit);
+    let digit_count = if hundreds > 0 { 4 } else { 3 };
+    let offsets = DIGIT_OFFSETS[digit_count - 3];
 
     for child in children {
-        let Ok((mut sprite, thermometer_number)) = thermometer_number_query.get_mut(*child) else {
+        let Ok((mut sprite, thermometer_number, mut transform, mut visibility)) =
+            thermometer_number_query.get_mut(*child)
+        else {
             continue;
         };
 
+        sprite.color = color;
+        transform.translation.x = offsets[thermometer_number.0 as usize];
+
+        if let Digit::Hundreds = thermometer_number.0 {
+            *visibility = if hundreds > 0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+
         let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
             continue;
         };
 
-        let digit = (match thermometer_number.0 {
-            Digit::Ones => thermometer.reading,
-            Digit::Tens => (thermometer.reading / 10.0).floor(),
-            Digit::Tenths => (thermometer.reading * 10.0).floor(),
-        }) as usize
-            % 10;
-
-        texture_atlas.index = digit;
+        texture_atlas.index = match thermometer_number.0 {
+            Digit::Hundreds => hundreds,
+            Digit::Tens => tens,
+            Digit::Ones => ones,
+            Digit::Tenths => tenths,
+        };
     }
 }
 
-pub fn thermometer(game_assets: Res<GameAssets>) -> impl Bundle {
+pub fn thermometer(game_assets: Res<GameAssets>, reading: Fever) -> impl Bundle {
     (
-        Sprite::from_atlas_image(
-            game_assets.thermometer.clone(),
-            TextureAtlas {
-                index: 0,
-                layout: game_assets.thermometer_layout.clone(),
-            },
-        ),
-        Thermometer {
-            reading: STARTING_FEVER,
-        },
+        Thermometer::new(reading),
         Transform::from_xyz(0.0, -48.0, 1.0).with_scale(Vec3::splat(2.0)),
         children![
+            (
+                Sprite::from_atlas_image(
+                    game_assets.thermometer_numbers.clone(),
+                    TextureAtlas {
+                        index: 0,
+                        layout: game_assets.thermometer_numbers_layout.clone(),
+                    }
+                ),
+                ThermometerNumber(Digit::Hundreds),
+                Transform::from_xyz(DIGIT_OFFSETS[0][Digit::Hundreds as usize], 0.0, 1.0),
+                Visibility::Hidden,
+            ),
             (
                 Sprite::from_atlas_image(
                     game_assets.thermometer_numbers.clone(),
@@ -97,7 +288,7 @@ pub fn thermometer(game_assets: Res<GameAssets>) -> impl Bundle {
                     }
                 ),
                 ThermometerNumber(Digit::Tens),
-                Transform::from_xyz(-9.0, 0.0, 1.0)
+                Transform::from_xyz(DIGIT_OFFSETS[0][Digit::Tens as usize], 0.0, 1.0)
             ),
             (
                 Sprite::from_atlas_image(
@@ -108,7 +299,7 @@ pub fn thermometer(game_assets: Res<GameAssets>) -> impl Bundle {
                     }
                 ),
                 ThermometerNumber(Digit::Ones),
-                Transform::from_xyz(-2.0, 0.0, 1.0)
+                Transform::from_xyz(DIGIT_OFFSETS[0][Digit::Ones as usize], 0.0, 1.0)
             ),
             (
                 Sprite::from_atlas_image(
@@ -119,12 +310,86 @@ pub fn thermometer(game_assets: Res<GameAssets>) -> impl Bundle {
                     }
                 ),
                 ThermometerNumber(Digit::Tenths),
-                Transform::from_xyz(6.0, 0.0, 1.0)
+                Transform::from_xyz(DIGIT_OFFSETS[0][Digit::Tenths as usize], 0.0, 1.0)
             )
         ],
     )
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, render_thermometer);
+    app.add_systems(
+        Update,
+        (
+            Thermometer::tween
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            (Thermometer::added, render_thermometer)
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Digits as they'd be read off the formatted number, e.g. "38.5" -> [0, 3, 8, 5]
+    fn expected_digits(degrees: f64) -> [usize; 4] {
+        let text = format!("{degrees:05.1}").replace('.', "");
+        let mut digits = [0; 4];
+
+        for (digit, character) in digits.iter_mut().zip(text.chars()) {
+            *digit = character.to_digit(10).unwrap() as usize;
+        }
+
+        digits
+    }
+
+    #[test]
+    fn digits_cover_the_fever_range() {
+        for tenths in 370..=410 {
+            assert_eq!(
+                Fever::from_tenths(tenths).digits(TemperatureUnit::Celsius),
+                expected_digits(tenths as f64 / 10.0),
+                "{tenths} tenths",
+            );
+        }
+    }
+
+    #[test]
+    fn digits_of_38_5_are_exact() {
+        assert_eq!(
+            Fever::from_tenths(385).digits(TemperatureUnit::Celsius),
+            [0, 3, 8, 5]
+        );
+    }
+
+    #[test]
+    fn converts_to_fahrenheit() {
+        let fahrenheit = |tenths| Fever::from_tenths(tenths).tenths_in(TemperatureUnit::Fahrenheit);
+
+        assert_eq!(fahrenheit(370), 986);
+        assert_eq!(fahrenheit(400), 1040);
+        assert_eq!(fahrenheit(410), 1058);
+    }
+
+    #[test]
+    fn fahrenheit_digits_cover_the_fever_range() {
+        for tenths in 370..=410 {
+            let degrees = (tenths as f64 / 10.0) * 9.0 / 5.0 + 32.0;
+
+            assert_eq!(
+                Fever::from_tenths(tenths).digits(TemperatureUnit::Fahrenheit),
+                expected_digits((degrees * 10.0).round() / 10.0),
+                "{tenths} tenths",
+            );
+        }
+
+        assert_eq!(
+            Fever::from_tenths(400).digits(TemperatureUnit::Fahrenheit),
+            [1, 0, 4, 0]
+        );
+    }
 }
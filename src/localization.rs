@@ -0,0 +1,251 @@
+//! Player-facing strings, looked up by key in a RON string table per language.
+//!
+//! Tables live in `assets/locales/<code>.strings.ron` and map keys to text. Text can contain
+//! `{name}` placeholders, filled in from the arguments of a [`LocalizedText`]. Anything missing
+//! from the current language falls back to English, and then to the key itself.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{AppSystems, settings::Settings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<StringTable>();
+    app.init_asset_loader::<StringTableLoader>();
+
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    app.insert_resource(Locale::new(&asset_server));
+
+    app.add_systems(
+        Update,
+        (
+            update_locale,
+            render_localized_text
+                .run_if(resource_changed::<Locale>.or(any_match_filter::<Changed<LocalizedText>>)),
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::Spanish];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+        }
+    }
+
+    /// The language's name in that language, so it can be found from any other.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+}
+
+#[derive(Asset, Debug, Default, Deserialize, TypePath)]
+#[serde(transparent)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Default, TypePath)]
+struct StringTableLoader;
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StringTable, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings.ron"]
+    }
+}
+
+/// Strings for the language picked in the settings. Changes whenever the language changes or a
+/// string table is (re)loaded.
+#[derive(Resource, Debug, PartialEq)]
+pub struct Locale {
+    fallback: HashMap<String, String>,
+    strings: HashMap<String, String>,
+    tables: HashMap<Language, Handle<StringTable>>,
+}
+
+impl Locale {
+    // String tables are loaded right away rather than on the loading screen, so that screen
+    // and the splash before it can be translated too.
+    fn new(asset_server: &AssetServer) -> Self {
+        Self {
+            fallback: HashMap::new(),
+            strings: HashMap::new(),
+            tables: Language::ALL
+                .into_iter()
+                .map(|language| {
+                    (
+                        language,
+                        asset_server.load(format!("locales/{}.strings.ron", language.code())),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Looks up `key` and fills in its `{name}` placeholders.
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        args.iter().fold(self.get(key), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+    }
+}
+
+/// Sets the entity's [`Text`] or [`Text2d`] to a string from the current [`Locale`], and keeps
+/// it up to date when the language changes.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct LocalizedText {
+    args: Vec<(&'static str, String)>,
+    is_plain: bool,
+    key: String,
+    localized_args: Vec<(&'static str, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            args: Vec::new(),
+            is_plain: false,
+            key: key.into(),
+            localized_args: Vec::new(),
+        }
+    }
+
+    /// Text shown as is in every language, e.g. numbers, names, and values built elsewhere.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            args: Vec::new(),
+            is_plain: true,
+            key: text.into(),
+            localized_args: Vec::new(),
+        }
+    }
+
+    pub fn with_arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+
+        self
+    }
+
+    /// Fills in a placeholder with another string from the table, e.g. a minigame's name.
+    pub fn with_localized_arg(mut self, name: &'static str, key: impl Into<String>) -> Self {
+        self.localized_args.push((name, key.into()));
+
+        self
+    }
+
+    pub fn render(&self, locale: &Locale) -> String {
+        if self.is_plain {
+            return self.key.clone();
+        }
+
+        let args = self
+            .args
+            .iter()
+            .cloned()
+            .chain(
+                self.localized_args
+                    .iter()
+                    .map(|(name, key)| (*name, locale.get(key))),
+            )
+            .collect::<Vec<_>>();
+
+        locale.format(&self.key, &args)
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+fn update_locale(
+    mut asset_events: MessageReader<AssetEvent<StringTable>>,
+    mut locale: ResMut<Locale>,
+    settings: Res<Settings>,
+    string_tables: Res<Assets<StringTable>>,
+) {
+    let has_tables_changed = asset_events.read().count() > 0;
+
+    if !has_tables_changed && !settings.is_changed() {
+        return;
+    }
+
+    let strings = |language: Language| {
+        locale
+            .tables
+            .get(&language)
+            .and_then(|handle| string_tables.get(handle))
+            .map(|string_table| string_table.0.clone())
+            .unwrap_or_default()
+    };
+
+    let new_locale = Locale {
+        fallback: strings(Language::default()),
+        strings: strings(settings.language),
+        tables: locale.tables.clone(),
+    };
+
+    locale.set_if_neq(new_locale);
+}
+
+fn render_localized_text(
+    locale: Res<Locale>,
+    localized_text_query: Query<(Ref<LocalizedText>, Option<&mut Text>, Option<&mut Text2d>)>,
+) {
+    for (localized_text, text, text_2d) in localized_text_query {
+        if !locale.is_changed() && !localized_text.is_changed() {
+            continue;
+        }
+
+        let value = localized_text.render(&locale);
+
+        if let Some(mut text) = text {
+            text.0 = value;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = value;
+        }
+    }
+}
@@ -3,14 +3,18 @@
 // Disable console on Windows for non-dev builds.
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
-// mod asset_tracking;
+mod asset_tracking;
 mod audio;
 // mod demo;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod display;
 mod game;
+mod localization;
 mod menus;
 mod screens;
+mod settings;
+mod storage;
 mod theme;
 
 use bevy::{asset::AssetMetaCheck, camera::ScalingMode, prelude::*};
@@ -32,7 +36,11 @@ impl Plugin for AppPlugin {
         app.insert_resource(ClearColor(Color::BLACK));
 
         // Set up loading state
-        app.add_loading_state(LoadingState::new(Screen::Loading).continue_to_state(Screen::Title));
+        app.add_loading_state(
+            LoadingState::new(Screen::Loading)
+                .continue_to_state(Screen::Title)
+                .on_failure_continue_to_state(Screen::LoadingFailed),
+        );
 
         // Add Bevy plugins.
         app.add_plugins(
@@ -58,12 +66,16 @@ impl Plugin for AppPlugin {
 
         // Add other plugins.
         app.add_plugins((
+            asset_tracking::plugin,
             audio::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            display::plugin,
             game::plugin,
+            localization::plugin,
             menus::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
@@ -109,7 +121,10 @@ struct Pause(pub bool);
 struct PausableSystems;
 
 fn app_is_loaded(screen: Res<State<Screen>>) -> bool {
-    screen.get() != &Screen::Splash && screen.get() != &Screen::Loading
+    !matches!(
+        screen.get(),
+        Screen::Splash | Screen::Loading | Screen::LoadingFailed
+    )
 }
 
 fn spawn_camera(mut commands: Commands) {
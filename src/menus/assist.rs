@@ -0,0 +1,120 @@
+//! The assist menu, reached from the settings menu.
+//!
+//! Runs played with any of these options are flagged and get their own high scores.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    menus::{
+        Menu,
+        settings::{
+            on_off, percent_value, setting_selector_widget, setting_toggle_widget, step_option,
+        },
+    },
+    settings::Settings,
+    theme::prelude::*,
+};
+
+const GAME_SPEEDS: [u32; 4] = [50, 60, 75, 100];
+const TIMER_LENGTHS: [u32; 4] = [100, 150, 200, 300];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Assist), spawn_assist_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Assist).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_assist_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Assist Menu", false),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Assist),
+        children![
+            widget::header("assist.title"),
+            assist_grid(),
+            widget::label("assist.note"),
+            widget::button("menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn assist_grid() -> impl Bundle {
+    (
+        Name::new("Assist Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: px(10),
+            column_gap: px(30),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label("assist.game_speed"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Game Speed Widget",
+                |settings| percent_value(settings.assist.game_speed),
+                cycle_game_speed(-1),
+                cycle_game_speed(1),
+            ),
+            (
+                widget::label("assist.timer_length"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Timer Length Widget",
+                |settings| percent_value(settings.assist.timer_length),
+                cycle_timer_length(-1),
+                cycle_timer_length(1),
+            ),
+            (
+                widget::label("assist.invincible_fever"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Invincible Fever Widget",
+                |settings| on_off(settings.assist.invincible_fever),
+                toggle_invincible_fever,
+            ),
+        ],
+    )
+}
+
+fn cycle_game_speed(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        settings.assist.game_speed =
+            step_option(&GAME_SPEEDS, settings.assist.game_speed, direction);
+    }
+}
+
+fn cycle_timer_length(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        settings.assist.timer_length =
+            step_option(&TIMER_LENGTHS, settings.assist.timer_length, direction);
+    }
+}
+
+fn toggle_invincible_fever(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.assist.invincible_fever = !settings.assist.invincible_fever;
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
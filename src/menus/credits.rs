@@ -3,7 +3,10 @@
 use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
 use bevy_asset_loader::prelude::*;
 
-use crate::{audio::music, menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::LoadCollection, audio::music, localization::LocalizedText, menus::Menu,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
@@ -12,9 +15,7 @@ pub(super) fn plugin(app: &mut App) {
         go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
     );
 
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<CreditsAssets>(),
-    );
+    app.load_collection::<CreditsAssets>();
     app.add_systems(OnEnter(Menu::Credits), start_credits_music);
 }
 
@@ -24,33 +25,36 @@ fn spawn_credits_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Credits),
         children![
-            widget::header("Created by"),
+            widget::header("credits.created_by"),
             created_by(),
-            widget::header("Assets"),
+            widget::header("credits.assets"),
             assets(),
-            widget::button("Back", go_back_on_click),
+            widget::button("menu.back", go_back_on_click),
         ],
     ));
 }
 
 fn created_by() -> impl Bundle {
-    grid(vec![["Wyatt Barnes", "Design/Gameplay/Art/Music"]])
+    grid(vec![[
+        LocalizedText::plain("Wyatt Barnes"),
+        LocalizedText::new("credits.roles"),
+    ]])
 }
 
 fn assets() -> impl Bundle {
     grid(vec![
         [
-            "\"Press Start 2P\" Font",
-            "cody@zone38.net (SIL OPEN FONT LICENSE V1.1)",
+            LocalizedText::new("credits.font"),
+            LocalizedText::plain("cody@zone38.net (SIL OPEN FONT LICENSE V1.1)"),
         ],
         [
-            "Bevy logo",
-            "All rights reserved by the Bevy Foundation, permission granted for splash screen use when unmodified",
+            LocalizedText::new("credits.bevy_logo"),
+            LocalizedText::new("credits.bevy_logo_license"),
         ],
     ])
 }
 
-fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
+fn grid(content: Vec<[LocalizedText; 2]>) -> impl Bundle {
     (
         Name::new("Grid"),
         Node {
@@ -0,0 +1,56 @@
+//! The difficulty menu, shown before a run starts.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::fever_rules::{FeverPreset, FeverRules},
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Difficulty), spawn_difficulty_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Difficulty).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_difficulty_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Difficulty Menu", false),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Difficulty),
+        children![
+            widget::header("difficulty.title"),
+            preset_button(FeverPreset::Easy),
+            preset_button(FeverPreset::Normal),
+            preset_button(FeverPreset::Hard),
+            widget::button("menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn preset_button(preset: FeverPreset) -> impl Bundle {
+    widget::button(preset.name_key(), start_run(preset))
+}
+
+fn start_run(
+    preset: FeverPreset,
+) -> impl Fn(On<Pointer<Click>>, ResMut<FeverRules>, ResMut<NextState<Screen>>) {
+    move |_: On<Pointer<Click>>,
+          mut fever_rules: ResMut<FeverRules>,
+          mut next_screen: ResMut<NextState<Screen>>| {
+        *fever_rules = preset.rules();
+        next_screen.set(Screen::Gameplay);
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
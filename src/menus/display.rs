@@ -0,0 +1,132 @@
+//! The display menu, reached from the settings menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    display::DisplayMode,
+    localization::LocalizedText,
+    menus::{
+        Menu,
+        settings::{on_off, setting_selector_widget, setting_toggle_widget, step_option},
+    },
+    settings::Settings,
+    theme::prelude::*,
+};
+
+const WINDOW_SCALES: [u32; 5] = [3, 4, 5, 6, 8];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Display), spawn_display_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Display).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_display_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Display Menu", false),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Display),
+        children![
+            widget::header("display.title"),
+            display_grid(),
+            widget::button("menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn display_grid() -> impl Bundle {
+    (
+        Name::new("Display Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: px(10),
+            column_gap: px(30),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label("display.window"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Window Widget",
+                |settings| settings.display_mode.name_key().into(),
+                toggle_display_mode,
+            ),
+            (
+                widget::label("display.window_scale"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Window Scale Widget",
+                |settings| {
+                    LocalizedText::new("display.scale").with_arg("value", settings.window_scale)
+                },
+                cycle_window_scale(-1),
+                cycle_window_scale(1),
+            ),
+            (
+                widget::label("display.vsync"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "VSync Widget",
+                |settings| on_off(settings.vsync),
+                toggle_vsync,
+            ),
+            (
+                widget::label("display.pixel_perfect"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Pixel Perfect Widget",
+                |settings| on_off(settings.pixel_perfect),
+                toggle_pixel_perfect,
+            ),
+        ],
+    )
+}
+
+fn toggle_display_mode(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.display_mode = match settings.display_mode {
+        DisplayMode::Windowed => DisplayMode::BorderlessFullscreen,
+        DisplayMode::BorderlessFullscreen => DisplayMode::Windowed,
+    };
+}
+
+fn cycle_window_scale(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        settings.window_scale = step_option(&WINDOW_SCALES, settings.window_scale, direction);
+    }
+}
+
+fn toggle_vsync(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.vsync = !settings.vsync;
+}
+
+fn toggle_pixel_perfect(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.pixel_perfect = !settings.pixel_perfect;
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
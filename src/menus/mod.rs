@@ -1,6 +1,9 @@
 //! The game's menus and transitions between them.
 
+mod assist;
 mod credits;
+mod difficulty;
+mod display;
 mod main;
 pub mod pause;
 mod settings;
@@ -11,7 +14,10 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        assist::plugin,
         credits::plugin,
+        difficulty::plugin,
+        display::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
@@ -23,7 +29,10 @@ pub enum Menu {
     #[default]
     None,
     Main,
+    Assist,
     Credits,
+    Difficulty,
+    Display,
     Settings,
     Pause,
 }
@@ -18,10 +18,10 @@ fn spawn_pause_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Pause),
         children![
-            widget::header("Game paused"),
-            widget::button("Continue", close_menu),
-            widget::button("Settings", open_settings_menu),
-            widget::button("To Title", quit_to_title),
+            widget::header("pause.title"),
+            widget::button("pause.continue", close_menu),
+            widget::button("menu.settings", open_settings_menu),
+            widget::button("menu.to_title", quit_to_title),
         ],
     ));
 }
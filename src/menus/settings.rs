@@ -1,10 +1,23 @@
 //! The settings menu.
 //!
-//! Additional settings and accessibility options should go here.
+//! Additional settings and accessibility options should go here. Display and assist options
+//! have their own pages in `display` and `assist`.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    ecs::system::IntoObserverSystem, input::common_conditions::input_just_pressed, prelude::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    localization::{Language, LocalizedText},
+    menus::Menu,
+    screens::Screen,
+    settings::{Settings, TemperatureUnit},
+    theme::{palette::PaletteKind, prelude::*},
+};
+
+const MAX_VOLUME: u32 = 300;
+const TEXT_SCALES: [u32; 4] = [75, 100, 125, 150];
+const VOLUME_STEP: u32 = 10;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -15,7 +28,12 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        update_setting_labels.run_if(
+            in_state(Menu::Settings)
+                .or(in_state(Menu::Assist))
+                .or(in_state(Menu::Display))
+                .and(resource_changed::<Settings>.or(any_match_filter::<Added<SettingLabel>>)),
+        ),
     );
 }
 
@@ -25,9 +43,11 @@ fn spawn_settings_menu(mut commands: Commands) {
         GlobalZIndex(2),
         DespawnOnExit(Menu::Settings),
         children![
-            widget::header("Settings"),
+            widget::header("settings.title"),
             settings_grid(),
-            widget::button("Back", go_back_on_click),
+            widget::button("settings.display", open_display_menu),
+            widget::button("settings.assist", open_assist_menu),
+            widget::button("menu.back", go_back_on_click),
         ],
     ));
 }
@@ -44,17 +64,178 @@ fn settings_grid() -> impl Bundle {
         },
         children![
             (
-                widget::label("Master Volume"),
+                widget::label("settings.language"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Language Widget",
+                |settings| LocalizedText::plain(settings.language.name()),
+                cycle_language(-1),
+                cycle_language(1),
+            ),
+            (
+                widget::label("settings.master_volume"),
                 Node {
                     justify_self: JustifySelf::End,
                     ..default()
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("settings.reduced_motion"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Reduced Motion Widget",
+                |settings| on_off(settings.reduced_motion),
+                toggle_reduced_motion,
+            ),
+            (
+                widget::label("settings.slow_motion"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Slow Motion Widget",
+                |settings| on_off(settings.slow_motion),
+                toggle_slow_motion,
+            ),
+            (
+                widget::label("settings.colors"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Colors Widget",
+                |settings| settings.palette.name_key().into(),
+                cycle_palette(-1),
+                cycle_palette(1),
+            ),
+            (
+                widget::label("settings.text_size"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_selector_widget(
+                "Text Size Widget",
+                |settings| percent_value(settings.text_scale),
+                cycle_text_scale(-1),
+                cycle_text_scale(1),
+            ),
+            (
+                widget::label("settings.readable_font"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Readable Font Widget",
+                |settings| on_off(settings.readable_font),
+                toggle_readable_font,
+            ),
+            (
+                widget::label("settings.temperature"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            setting_toggle_widget(
+                "Temperature Widget",
+                |settings| LocalizedText::plain(settings.temperature_unit.symbol()),
+                toggle_temperature_unit,
+            ),
+        ],
+    )
+}
+
+/// A `< value >` selector for a setting with two values. Both arrows run `action`.
+pub(super) fn setting_toggle_widget<E, B, M, I>(
+    name: &'static str,
+    display: fn(&Settings) -> LocalizedText,
+    action: I,
+) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M> + Clone,
+{
+    setting_selector_widget(name, display, action.clone(), action)
+}
+
+/// A `< value >` selector for a setting, where the arrows run `previous` and `next`.
+pub(super) fn setting_selector_widget<E, B, M, P, N>(
+    name: &'static str,
+    display: fn(&Settings) -> LocalizedText,
+    previous: P,
+    next: N,
+) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    P: IntoObserverSystem<E, B, M>,
+    N: IntoObserverSystem<E, B, M>,
+{
+    (
+        Name::new(name),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(
+                    widget::label(LocalizedText::plain("")),
+                    SettingLabel(display)
+                )],
+            ),
+            widget::button_small(">", next),
         ],
     )
 }
 
+// The option `direction` steps away from `current`, stopping at either end. Values that aren't
+// one of the options (e.g. from a hand-edited settings file) snap to the closest one.
+pub(super) fn step_option(options: &[u32], current: u32, direction: isize) -> u32 {
+    let index = options
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, option)| option.abs_diff(current))
+        .map_or(0, |(index, _)| index);
+
+    options[index
+        .saturating_add_signed(direction)
+        .min(options.len() - 1)]
+}
+
+pub(super) fn on_off(value: bool) -> LocalizedText {
+    LocalizedText::new(if value { "settings.on" } else { "settings.off" })
+}
+
+pub(super) fn percent_value(value: u32) -> LocalizedText {
+    LocalizedText::new("settings.percent").with_arg("value", value)
+}
+
 fn global_volume_widget() -> impl Bundle {
     (
         Name::new("Global Volume Widget"),
@@ -63,7 +244,7 @@ fn global_volume_widget() -> impl Bundle {
             ..default()
         },
         children![
-            widget::button("-", lower_global_volume),
+            widget::button("settings.volume_down", lower_global_volume),
             (
                 Name::new("Current Volume"),
                 Node {
@@ -71,36 +252,94 @@ fn global_volume_widget() -> impl Bundle {
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                children![(
+                    widget::label(LocalizedText::plain("")),
+                    SettingLabel(|settings| percent_value(settings.volume))
+                )],
             ),
-            widget::button("+", raise_global_volume),
+            widget::button("settings.volume_up", raise_global_volume),
         ],
     )
 }
 
-const MIN_VOLUME: f32 = 0.0;
-const MAX_VOLUME: f32 = 3.0;
+fn lower_global_volume(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.volume = settings.volume.saturating_sub(VOLUME_STEP);
+}
+
+fn raise_global_volume(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.volume = (settings.volume + VOLUME_STEP).min(MAX_VOLUME);
+}
+
+fn toggle_reduced_motion(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.reduced_motion = !settings.reduced_motion;
+}
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn toggle_slow_motion(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.slow_motion = !settings.slow_motion;
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn cycle_palette(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        let kinds = PaletteKind::ALL;
+        let index = kinds
+            .iter()
+            .position(|kind| *kind == settings.palette)
+            .unwrap_or_default();
+
+        settings.palette =
+            kinds[(index as isize + direction).rem_euclid(kinds.len() as isize) as usize];
+    }
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct GlobalVolumeLabel;
+fn cycle_language(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        let languages = Language::ALL;
+        let index = languages
+            .iter()
+            .position(|language| *language == settings.language)
+            .unwrap_or_default();
+
+        settings.language =
+            languages[(index as isize + direction).rem_euclid(languages.len() as isize) as usize];
+    }
+}
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+fn cycle_text_scale(direction: isize) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        settings.text_scale = step_option(&TEXT_SCALES, settings.text_scale, direction);
+    }
+}
+
+fn toggle_readable_font(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.readable_font = !settings.readable_font;
+}
+
+fn toggle_temperature_unit(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.temperature_unit = match settings.temperature_unit {
+        TemperatureUnit::Celsius => TemperatureUnit::Fahrenheit,
+        TemperatureUnit::Fahrenheit => TemperatureUnit::Celsius,
+    };
+}
+
+/// Shows the current value of a setting, formatted by the given function.
+#[derive(Component)]
+struct SettingLabel(fn(&Settings) -> LocalizedText);
+
+fn update_setting_labels(
+    settings: Res<Settings>,
+    label_query: Query<(&mut LocalizedText, &SettingLabel)>,
 ) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+    for (mut localized_text, setting_label) in label_query {
+        localized_text.set_if_neq((setting_label.0)(&settings));
+    }
+}
+
+fn open_display_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Display);
+}
+
+fn open_assist_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Assist);
 }
 
 fn go_back_on_click(
@@ -1,32 +1,175 @@
 //! A loading screen during which game assets are loaded if necessary.
 //! This reduces stuttering, especially for audio on Wasm.
+//!
+//! If any asset fails to load, the failure screen lists them and lets the player try again, or
+//! continue with placeholders and without the minigames that need the failed assets.
 
-use bevy::prelude::*;
+use bevy::{asset::UntypedAssetLoadFailedEvent, ecs::spawn::SpawnIter, prelude::*};
 
-// use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
-use crate::{screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::{LoadingProgress, substitute_placeholders},
+    game::minigames::disable_failed_minigames,
+    localization::LocalizedText,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+const PROGRESS_BAR_BACKGROUND: Color = Color::srgb(0.2, 0.2, 0.2);
+const PROGRESS_BAR_HEIGHT: f32 = 12.0;
+const PROGRESS_BAR_WIDTH: f32 = 300.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
+    app.add_systems(
+        Update,
+        update_progress_bar.run_if(in_state(Screen::Loading)),
+    );
 
-    // app.add_systems(
-    //     Update,
-    //     enter_gameplay_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
-    // );
+    app.add_systems(OnEnter(Screen::LoadingFailed), spawn_loading_failed_screen);
+    app.add_systems(
+        Update,
+        enter_loading_after_reload
+            .run_if(in_state(Screen::LoadingFailed).and(resource_exists::<RetryingLoad>)),
+    );
 }
 
 fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         widget::ui_root("Loading Screen", true),
         DespawnOnExit(Screen::Loading),
-        children![widget::label("Loading...")],
+        children![widget::label("loading.title"), progress_bar()],
+    ));
+}
+
+#[derive(Component)]
+struct ProgressBarFill;
+
+fn progress_bar() -> impl Bundle {
+    (
+        Name::new("Progress Bar"),
+        Node {
+            width: px(PROGRESS_BAR_WIDTH),
+            height: px(PROGRESS_BAR_HEIGHT),
+            ..default()
+        },
+        BackgroundColor(PROGRESS_BAR_BACKGROUND),
+        children![(
+            Name::new("Progress Bar Fill"),
+            ProgressBarFill,
+            Node {
+                width: percent(0),
+                height: percent(100),
+                ..default()
+            },
+            BackgroundColor(Palette::DEFAULT.accent),
+            ThemeColor::Accent,
+        )],
+    )
+}
+
+fn update_progress_bar(
+    asset_server: Res<AssetServer>,
+    fill_query: Query<&mut Node, With<ProgressBarFill>>,
+    loading_progress: Res<LoadingProgress>,
+) {
+    let (done, total) = loading_progress.counts(&asset_server);
+    let fraction = if total == 0 {
+        1.0
+    } else {
+        done as f32 / total as f32
+    };
+
+    for mut node in fill_query {
+        node.width = percent(fraction * 100.0);
+    }
+}
+
+fn spawn_loading_failed_screen(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    loading_progress: Res<LoadingProgress>,
+) {
+    let failures = loading_progress
+        .failures(&asset_server)
+        .into_iter()
+        .map(|(collection, path)| {
+            widget::label(
+                LocalizedText::new("loading.failed_asset")
+                    .with_arg("collection", collection)
+                    .with_arg("path", path),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    commands.spawn((
+        widget::ui_root("Loading Failed Screen", true),
+        DespawnOnExit(Screen::LoadingFailed),
+        children![
+            widget::header("loading.failed"),
+            widget::label("loading.failed_note"),
+            (
+                Name::new("Failed Assets"),
+                Node {
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(10),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(failures.into_iter())),
+            ),
+            (
+                Name::new("Failure Actions"),
+                Node {
+                    column_gap: px(20),
+                    ..default()
+                },
+                children![
+                    widget::button("loading.retry", retry_loading),
+                    widget::button("loading.continue", continue_without_failed),
+                ],
+            ),
+        ],
     ));
 }
 
-// fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
-//     next_screen.set(Screen::Gameplay);
-// }
+fn retry_loading(
+    _: On<Pointer<Click>>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    loading_progress: Res<LoadingProgress>,
+) {
+    loading_progress.reload_failed(&asset_server);
+    commands.insert_resource(RetryingLoad);
+}
+
+/// Marks that the failed assets are being loaded again. Going back to the loading screen waits
+/// until they are no longer marked as failed, or the loading state would see the old failures and
+/// give up straight away.
+#[derive(Resource)]
+struct RetryingLoad;
 
-// fn all_assets_loaded(resource_handles: Res<ResourceHandles>) -> bool {
-//     resource_handles.is_all_done()
-// }
+fn enter_loading_after_reload(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    mut failure_reader: MessageReader<UntypedAssetLoadFailedEvent>,
+    loading_progress: Res<LoadingProgress>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    // An asset can fail again before it's ever seen loading, so a new failure counts as done too
+    let failed_again = failure_reader.read().count() > 0;
+
+    if failed_again || loading_progress.failures(&asset_server).is_empty() {
+        commands.remove_resource::<RetryingLoad>();
+        next_screen.set(Screen::Loading);
+    }
+}
+
+fn continue_without_failed(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.run_system_cached(disable_failed_minigames);
+    commands.run_system_cached(substitute_placeholders);
+    next_screen.set(Screen::Title);
+}
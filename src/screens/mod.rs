@@ -25,5 +25,7 @@ pub enum Screen {
     Splash,
     Title,
     Loading,
+    /// Some assets failed to load, shown with a list of them and a retry button.
+    LoadingFailed,
     Gameplay,
 }
@@ -0,0 +1,140 @@
+//! Player-facing settings that affect gameplay and presentation.
+//!
+//! Settings are loaded once at startup and saved whenever they change.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{display::DisplayMode, localization::Language, storage, theme::palette::PaletteKind};
+
+const STORAGE_NAME: &str = "settings";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(storage::load::<Settings>(STORAGE_NAME).unwrap_or_default());
+    app.add_systems(
+        Last,
+        save_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// Options that make the game easier. Runs that use any of them get their own high scores.
+    pub assist: Assist,
+    /// Windowed or borderless fullscreen.
+    pub display_mode: DisplayMode,
+    /// Language for menus and in-game text.
+    pub language: Language,
+    /// Colors for the UI, timer bar, and warnings.
+    pub palette: PaletteKind,
+    /// Render the game at its own resolution and scale it up by a whole number with black bars,
+    /// instead of stretching it to fit.
+    pub pixel_perfect: bool,
+    /// Draw text in Bevy's built-in font instead of the pixel font.
+    pub readable_font: bool,
+    /// Replace transitions with a cut, stop flashing warnings and camera effects, and slow down
+    /// sprite animations.
+    pub reduced_motion: bool,
+    /// Run gameplay at a reduced speed, leaving menus untouched. Unlike the assist game speed,
+    /// it doesn't count as an assisted run.
+    pub slow_motion: bool,
+    /// Unit for displayed temperatures. Fever thresholds are always in Celsius.
+    pub temperature_unit: TemperatureUnit,
+    /// Text size in percent.
+    pub text_scale: u32,
+    /// Wait for the display's refresh before showing a frame.
+    pub vsync: bool,
+    /// Master volume in percent.
+    pub volume: u32,
+    /// Windowed size as a multiple of the game's resolution.
+    pub window_scale: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            assist: Assist::default(),
+            display_mode: DisplayMode::default(),
+            language: Language::default(),
+            palette: PaletteKind::default(),
+            pixel_perfect: false,
+            readable_font: false,
+            reduced_motion: false,
+            slow_motion: false,
+            temperature_unit: TemperatureUnit::default(),
+            text_scale: 100,
+            volume: 100,
+            vsync: true,
+            window_scale: 5,
+        }
+    }
+}
+
+impl Settings {
+    pub fn text_scale_factor(&self) -> f32 {
+        self.text_scale as f32 / 100.0
+    }
+
+    pub fn volume_factor(&self) -> f32 {
+        self.volume as f32 / 100.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Assist {
+    /// Gameplay speed in percent, leaving menus untouched.
+    pub game_speed: u32,
+    /// The fever stops just short of game over.
+    pub invincible_fever: bool,
+    /// Minigame timer length in percent.
+    pub timer_length: u32,
+}
+
+impl Default for Assist {
+    fn default() -> Self {
+        Self {
+            game_speed: 100,
+            invincible_fever: false,
+            timer_length: 100,
+        }
+    }
+}
+
+impl Assist {
+    pub fn is_enabled(&self) -> bool {
+        self != &Self::default()
+    }
+
+    pub fn game_speed_scale(&self) -> f32 {
+        self.game_speed as f32 / 100.0
+    }
+
+    pub fn timer_length_scale(&self) -> f32 {
+        self.timer_length as f32 / 100.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Celsius => "C",
+            Self::Fahrenheit => "F",
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if let Err(error) = storage::save(STORAGE_NAME, &*settings) {
+        warn!("Failed to save settings: {error}");
+    }
+}
@@ -0,0 +1,78 @@
+//! Small RON files that outlive a session, such as settings and high scores. They go in the
+//! user's config directory on native, and in local storage on the web.
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Reads a saved value, or `None` if it's missing or can't be read.
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let contents = backend::read(name)?;
+
+    ron::from_str(&contents)
+        .inspect_err(|error| warn!("Ignoring invalid save \"{name}\": {error}"))
+        .ok()
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result {
+    backend::write(
+        name,
+        &ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?,
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::{fs, path::PathBuf};
+
+    use bevy::prelude::*;
+
+    fn path(name: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("fever_dream")
+                .join(format!("{name}.ron")),
+        )
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        fs::read_to_string(path(name)?).ok()
+    }
+
+    pub fn write(name: &str, contents: &str) -> Result {
+        let path = path(name).ok_or("no config directory")?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use bevy::prelude::*;
+
+    fn key(name: &str) -> String {
+        format!("fever_dream.{name}")
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(name: &str) -> Option<String> {
+        local_storage()?.get_item(&key(name)).ok()?
+    }
+
+    pub fn write(name: &str, contents: &str) -> Result {
+        local_storage()
+            .ok_or("no local storage")?
+            .set_item(&key(name), contents)
+            .map_err(|_| "failed to write to local storage")?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+//! Text that follows the text-size and readable-font settings.
+
+use bevy::prelude::*;
+
+use crate::{AppSystems, game::game_assets::GameAssets, settings::Settings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, apply_scaled_fonts.in_set(AppSystems::Update));
+}
+
+/// Base font size before the text-size setting is applied. The font itself is the game's pixel
+/// font, or Bevy's built-in font when the readable font is turned on or the game's assets haven't
+/// loaded yet.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+#[require(TextFont)]
+pub struct ScaledFont(pub f32);
+
+fn apply_scaled_fonts(
+    game_assets: Option<Res<GameAssets>>,
+    scaled_font_query: Query<(Ref<ScaledFont>, &mut TextFont)>,
+    settings: Res<Settings>,
+) {
+    let font = match &game_assets {
+        Some(game_assets) if !settings.readable_font => game_assets.font.clone(),
+        _ => Handle::default(),
+    };
+    let font_changed = settings.is_changed()
+        || game_assets
+            .as_ref()
+            .is_some_and(|game_assets| game_assets.is_added());
+
+    for (scaled_font, mut text_font) in scaled_font_query {
+        if !font_changed && !scaled_font.is_added() {
+            continue;
+        }
+
+        text_font.font = font.clone();
+        text_font.font_size = scaled_font.0 * settings.text_scale_factor();
+    }
+}
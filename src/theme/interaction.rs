@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use crate::{audio::sound_effect, screens::Screen};
+use crate::{asset_tracking::LoadCollection, audio::sound_effect};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(apply_interaction_palette_on_click);
@@ -9,9 +9,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(apply_interaction_palette_on_out);
     app.add_observer(apply_interaction_palette_on_release);
 
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<InteractionAssets>(),
-    );
+    app.load_collection::<InteractionAssets>();
     app.add_observer(play_sound_effect_on_click);
     app.add_observer(play_sound_effect_on_over);
 }
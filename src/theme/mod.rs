@@ -3,17 +3,28 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub mod font;
 pub mod interaction;
 pub mod palette;
 pub mod widget;
 
 #[allow(unused_imports)]
 pub mod prelude {
-    pub use super::{interaction::InteractionPalette, palette as ui_palette, widget};
+    pub use super::{
+        font::ScaledFont,
+        interaction::InteractionPalette,
+        palette::{Palette, ThemeColor},
+        widget,
+    };
 }
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((interaction::plugin, widget::plugin));
+    app.add_plugins((
+        font::plugin,
+        interaction::plugin,
+        palette::plugin,
+        widget::plugin,
+    ));
 }
@@ -1,19 +1,219 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-/// #ddd369
-pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
-
-/// #fcfbcc
-pub const HEADER_TEXT: Color = Color::srgb(0.988, 0.984, 0.800);
-
-/// #ececec
-pub const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
-/// #4666bf
-// pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
-pub const BUTTON_BACKGROUND: Color = Color::srgb(0.0, 0.0, 0.0);
-/// #6299d1
-// pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
-pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.1, 0.1, 0.1);
-/// #3d4999
-// pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
-pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.2, 0.2, 0.2);
+use crate::{settings::Settings, theme::interaction::InteractionPalette};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Palette>();
+
+    app.add_systems(
+        Update,
+        (
+            sync_palette.run_if(resource_changed::<Settings>),
+            apply_theme_colors
+                .run_if(resource_changed::<Palette>.or(any_match_filter::<Added<ThemeColor>>)),
+        )
+            .chain(),
+    );
+}
+
+/// Colors for UI and other non-sprite elements, picked in the settings.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub label_text: Color,
+    pub header_text: Color,
+    pub button_text: Color,
+    pub button_background: Color,
+    pub button_hovered_background: Color,
+    pub button_pressed_background: Color,
+    /// Behind text drawn over the game, e.g. the game UI and key caps.
+    pub text_background: Color,
+    /// Timer bar, thermometer warning flash, and fever graph.
+    pub accent: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Palette {
+    pub const DEFAULT: Self = Self {
+        // #ddd369
+        label_text: Color::srgb(0.867, 0.827, 0.412),
+        // #fcfbcc
+        header_text: Color::srgb(0.988, 0.984, 0.800),
+        // #ececec
+        button_text: Color::srgb(0.925, 0.925, 0.925),
+        button_background: Color::srgb(0.0, 0.0, 0.0),
+        button_hovered_background: Color::srgb(0.1, 0.1, 0.1),
+        button_pressed_background: Color::srgb(0.2, 0.2, 0.2),
+        text_background: Color::BLACK,
+        // #e35100
+        accent: Color::srgb_u8(227, 81, 0),
+    };
+
+    pub const HIGH_CONTRAST: Self = Self {
+        label_text: Color::srgb_u8(255, 230, 0),
+        header_text: Color::WHITE,
+        button_text: Color::WHITE,
+        button_background: Color::BLACK,
+        button_hovered_background: Color::srgb_u8(0, 0, 170),
+        button_pressed_background: Color::srgb_u8(0, 80, 255),
+        text_background: Color::BLACK,
+        accent: Color::srgb_u8(255, 230, 0),
+    };
+
+    // The color-vision palettes keep the default layout but move the accent (and, for
+    // tritanopia, the yellow label text) to Okabe-Ito colors that stay distinct from white.
+
+    pub const DEUTERANOPIA: Self = Self {
+        accent: Color::srgb_u8(0, 114, 178),
+        ..Self::DEFAULT
+    };
+
+    pub const PROTANOPIA: Self = Self {
+        accent: Color::srgb_u8(86, 180, 233),
+        ..Self::DEFAULT
+    };
+
+    pub const TRITANOPIA: Self = Self {
+        label_text: Color::srgb_u8(204, 121, 167),
+        accent: Color::srgb_u8(213, 94, 0),
+        ..Self::DEFAULT
+    };
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum PaletteKind {
+    #[default]
+    Default,
+    HighContrast,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl PaletteKind {
+    pub const ALL: [Self; 5] = [
+        Self::Default,
+        Self::HighContrast,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+    ];
+
+    /// String key for the palette's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Self::Default => "palette.default",
+            Self::HighContrast => "palette.high_contrast",
+            Self::Deuteranopia => "palette.deuteranopia",
+            Self::Protanopia => "palette.protanopia",
+            Self::Tritanopia => "palette.tritanopia",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            Self::Default => Palette::DEFAULT,
+            Self::HighContrast => Palette::HIGH_CONTRAST,
+            Self::Deuteranopia => Palette::DEUTERANOPIA,
+            Self::Protanopia => Palette::PROTANOPIA,
+            Self::Tritanopia => Palette::TRITANOPIA,
+        }
+    }
+}
+
+/// Which palette color an entity uses. The color is applied on spawn and again whenever the
+/// palette changes, to whichever of the matching components the entity has.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub enum ThemeColor {
+    /// [`TextColor`]
+    LabelText,
+    /// [`TextColor`]
+    HeaderText,
+    /// [`TextColor`]
+    ButtonText,
+    /// [`BackgroundColor`] and [`InteractionPalette`]
+    Button,
+    /// [`TextBackgroundColor`]
+    TextBackground,
+    /// [`Sprite`] or [`BackgroundColor`]
+    Accent,
+}
+
+fn sync_palette(mut palette: ResMut<Palette>, settings: Res<Settings>) {
+    palette.set_if_neq(settings.palette.palette());
+}
+
+fn apply_theme_colors(
+    palette: Res<Palette>,
+    theme_color_query: Query<(
+        Ref<ThemeColor>,
+        Option<&mut TextColor>,
+        Option<&mut TextBackgroundColor>,
+        Option<&mut BackgroundColor>,
+        Option<&mut InteractionPalette>,
+        Option<&mut Sprite>,
+    )>,
+) {
+    for (
+        theme_color,
+        text_color,
+        text_background_color,
+        background_color,
+        interaction_palette,
+        sprite,
+    ) in theme_color_query
+    {
+        // Leave existing entities alone (e.g. a hovered button) unless the palette changed
+        if !palette.is_changed() && !theme_color.is_added() {
+            continue;
+        }
+
+        let text_color_value = match *theme_color {
+            ThemeColor::LabelText => Some(palette.label_text),
+            ThemeColor::HeaderText => Some(palette.header_text),
+            ThemeColor::ButtonText => Some(palette.button_text),
+            _ => None,
+        };
+
+        if let (Some(mut text_color), Some(color)) = (text_color, text_color_value) {
+            text_color.0 = color;
+        }
+
+        match *theme_color {
+            ThemeColor::LabelText | ThemeColor::HeaderText | ThemeColor::ButtonText => {}
+            ThemeColor::Button => {
+                if let Some(mut interaction_palette) = interaction_palette {
+                    *interaction_palette = InteractionPalette {
+                        none: palette.button_background,
+                        hovered: palette.button_hovered_background,
+                        pressed: palette.button_pressed_background,
+                    };
+                }
+
+                if let Some(mut background_color) = background_color {
+                    background_color.0 = palette.button_background;
+                }
+            }
+            ThemeColor::TextBackground => {
+                if let Some(mut text_background_color) = text_background_color {
+                    text_background_color.0 = palette.text_background;
+                }
+            }
+            ThemeColor::Accent => {
+                if let Some(mut sprite) = sprite {
+                    sprite.color = palette.accent;
+                }
+
+                if let Some(mut background_color) = background_color {
+                    background_color.0 = palette.accent;
+                }
+            }
+        }
+    }
+}
@@ -10,21 +10,21 @@ use bevy_asset_loader::prelude::*;
 
 use crate::{
     AppSystems, app_is_loaded,
-    game::game_assets::GameAssets,
-    screens::Screen,
-    theme::{interaction::InteractionPalette, palette::*},
+    asset_tracking::LoadCollection,
+    localization::LocalizedText,
+    theme::{
+        font::ScaledFont,
+        interaction::InteractionPalette,
+        palette::{Palette, ThemeColor},
+    },
 };
 
 #[derive(Component)]
 struct UiRoot(bool);
 
-#[derive(Component)]
-struct Widget;
-
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>, hide_background: bool) -> impl Bundle {
     (
-        Widget,
         UiRoot(hide_background),
         Name::new(name),
         Node {
@@ -43,36 +43,38 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>, hide_background: bool) -> imp
 }
 
 /// A simple header label. Bigger than [`label`].
-pub fn header(text: impl Into<String>) -> impl Bundle {
+pub fn header(text: impl Into<LocalizedText>) -> impl Bundle {
     (
-        Widget,
         Name::new("Header"),
-        Text(text.into()),
-        TextFont::from_font_size(40.0),
-        TextColor(HEADER_TEXT),
+        Text::default(),
+        text.into(),
+        ScaledFont(40.0),
+        TextColor(Palette::DEFAULT.header_text),
+        ThemeColor::HeaderText,
     )
 }
 
 /// A simple text label.
-pub fn label(text: impl Into<String>) -> impl Bundle {
+pub fn label(text: impl Into<LocalizedText>) -> impl Bundle {
     (
-        Widget,
         Name::new("Label"),
-        Text(text.into()),
-        TextFont::from_font_size(18.0),
-        TextColor(LABEL_TEXT),
+        Text::default(),
+        text.into(),
+        ScaledFont(18.0),
+        TextColor(Palette::DEFAULT.label_text),
+        ThemeColor::LabelText,
     )
 }
 
 /// A large rounded button with text and an action defined as an [`Observer`].
-pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+pub fn button<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
 where
     E: EntityEvent,
     B: Bundle,
     I: IntoObserverSystem<E, B, M>,
 {
     button_base(
-        text,
+        text.into(),
         action,
         Node {
             width: px(200),
@@ -84,7 +86,8 @@ where
     )
 }
 
-/// A small square button with text and an action defined as an [`Observer`].
+/// A small square button with text and an action defined as an [`Observer`]. The text is
+/// meant to be a symbol, so it isn't translated.
 pub fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
     E: EntityEvent,
@@ -92,7 +95,7 @@ where
     I: IntoObserverSystem<E, B, M>,
 {
     button_base(
-        text,
+        LocalizedText::plain(text),
         action,
         Node {
             width: px(30),
@@ -106,7 +109,7 @@ where
 
 /// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
 fn button_base<E, B, M, I>(
-    text: impl Into<String>,
+    text: LocalizedText,
     action: I,
     button_bundle: impl Bundle,
 ) -> impl Bundle
@@ -115,7 +118,6 @@ where
     B: Bundle,
     I: IntoObserverSystem<E, B, M>,
 {
-    let text = text.into();
     let action = IntoObserverSystem::into_system(action);
     (
         Name::new("Button"),
@@ -125,18 +127,20 @@ where
                 .spawn((
                     Name::new("Button Inner"),
                     Button,
-                    BackgroundColor(BUTTON_BACKGROUND),
+                    BackgroundColor(Palette::DEFAULT.button_background),
                     InteractionPalette {
-                        none: BUTTON_BACKGROUND,
-                        hovered: BUTTON_HOVERED_BACKGROUND,
-                        pressed: BUTTON_PRESSED_BACKGROUND,
+                        none: Palette::DEFAULT.button_background,
+                        hovered: Palette::DEFAULT.button_hovered_background,
+                        pressed: Palette::DEFAULT.button_pressed_background,
                     },
+                    ThemeColor::Button,
                     children![(
-                        Widget,
                         Name::new("Button Text"),
-                        Text(text),
-                        TextFont::from_font_size(16.0),
-                        TextColor(BUTTON_TEXT),
+                        Text::default(),
+                        text,
+                        ScaledFont(16.0),
+                        TextColor(Palette::DEFAULT.button_text),
+                        ThemeColor::ButtonText,
                         // Don't bubble picking events from the text up to the button.
                         Pickable::IGNORE,
                     )],
@@ -147,15 +151,6 @@ where
     )
 }
 
-fn widget_font_added(
-    game_assets: Res<GameAssets>,
-    text_font_query: Query<&mut TextFont, Added<Widget>>,
-) {
-    for mut text_font in text_font_query {
-        text_font.font = game_assets.font.clone();
-    }
-}
-
 fn ui_root_added(
     mut commands: Commands,
     ui_root_query: Query<(Entity, &UiRoot), Added<UiRoot>>,
@@ -179,13 +174,11 @@ struct WidgetAssets {
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(Screen::Loading).load_collection::<WidgetAssets>(),
-    );
+    app.load_collection::<WidgetAssets>();
 
     app.add_systems(
         Update,
-        (ui_root_added, widget_font_added)
+        ui_root_added
             .in_set(AppSystems::Update)
             .run_if(app_is_loaded),
     );